@@ -1,12 +1,22 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fmt::Write;
 use std::fs;
 use std::path::Path;
 
+/// The feature under which registers from the `registers_beta` table are generated.
+const BETA_FEATURE: &str = "beta-registers";
+
+// The expansion of ranged names is shared with the runtime `RegisterMap`.
+include!("src/core/ranged_name.rs");
+
+/// Overrides the bundled constants file with a newer one, such that the registers of newer
+/// firmware may be generated without forking the crate. An absolute path is expected.
+const CONSTANTS_PATH_VAR: &str = "LABJACK_CONSTANTS_PATH";
+
 const CODEGEN_HEADER: &str = r#"// Codegen - @bennjii 2025 Sourced @ labjack/ljm_constants.json
 // All required attributes
 use crate::prelude::*;
@@ -31,12 +41,13 @@ fn uppercase_to_pascal_case(input: &str) -> String {
 
 fn main() {
     // Path to the ljm_constants.json file
-    let input_file = "./resources/ljm_constants.json";
+    let input_file = env::var(CONSTANTS_PATH_VAR)
+        .unwrap_or_else(|_| "./resources/ljm_constants.json".to_string());
     let out_dir = env::var("OUT_DIR").unwrap();
     let output_file = Path::new(&out_dir).join("codegen.rs");
 
     // Read and parse the JSON file
-    let content = fs::read_to_string(input_file).expect("Failed to read JSON file");
+    let content = fs::read_to_string(&input_file).expect("Failed to read JSON file");
     let data: serde_json::Value =
         serde_json::from_str(&content).expect("Failed to parse JSON file");
 
@@ -54,95 +65,38 @@ fn main() {
             r#"
 // LabJack Constants Version: {}
 // Support URL: {}
+
+/// The version of the LabJack constants file the registers were generated from.
+pub const CONSTANTS_VERSION: &str = {:?};
 "#,
-            support_map.version, support_map.support_url
+            support_map.version, support_map.support_url, support_map.version
         ))
         .unwrap();
 
     let mut all_register_names = vec![];
+    let mut all_aliases = vec![];
 
     if let Some(registers) = data.get("registers").and_then(|r| r.as_array()) {
-        for reg in registers {
-            // We cannot proceed if these properties do not exist, hence panic.
-            let name = reg.get("name").unwrap().as_str().unwrap();
-            let address = reg.get("address").unwrap().as_u64().unwrap();
-            let r#type = reg.get("type").unwrap().as_str().unwrap();
-            let desc = reg
-                .get("description")
-                .map(|v| v.as_str().unwrap())
-                .unwrap_or("No Description");
-
-            let tags = reg
-                .get("tags")
-                .map(|tags| tags.as_array().unwrap().to_vec())
-                .unwrap_or(vec![])
-                .iter()
-                .map(|v| v.as_str().unwrap().to_string())
-                .collect::<Vec<_>>();
-
-            let access_control =
-                crate::AccessControl::try_from(reg.get("readwrite").unwrap().as_str().unwrap())
-                    .unwrap();
-
-            let all_devices = reg.get("devices").unwrap().as_array().unwrap();
-            let mut devices = vec![];
-            for device in all_devices {
-                devices.push(
-                    crate::DeviceCompat::try_from(device).unwrap_or_else(|_| {
-                        panic!("Could not deserialise device note: {device:?}")
-                    }),
-                );
-            }
+        generate_registers(
+            &mut output,
+            registers,
+            false,
+            &support_map,
+            &mut all_register_names,
+            &mut all_aliases,
+        );
+    }
 
-            if let Some((base_name, range, suffix)) =
-                parse_name_with_range_and_optional_suffix(name)
-            {
-                for i in range {
-                    let expanded_name = if suffix.is_empty() {
-                        format!("{}{}", base_name, i)
-                    } else {
-                        format!("{}{}_{}", base_name, i, suffix)
-                    };
-
-                    all_register_names.push(expanded_name.clone());
-
-                    generate_register(
-                        &mut output,
-                        Register {
-                            data_type: decode_type(r#type),
-                            name: expanded_name.as_str(),
-                            base_address: address,
-                            offset: Some(i),
-                            access_control: &access_control,
-                            desc,
-                            devices: &devices,
-                            tags: &tags,
-                            default: None,
-                        },
-                        &support_map,
-                    );
-                }
-            } else {
-                all_register_names.push(name.to_string());
-
-                // The case when the register does not contain
-                generate_register(
-                    &mut output,
-                    Register {
-                        name,
-                        base_address: address,
-                        data_type: decode_type(r#type),
-                        offset: None,
-                        access_control: &access_control,
-                        desc,
-                        devices: &devices,
-                        tags: &tags,
-                        default: None,
-                    },
-                    &support_map,
-                );
-            }
-        }
+    // Beta registers are subject to change, so are only generated on request.
+    if let Some(registers) = data.get("registers_beta").and_then(|r| r.as_array()) {
+        generate_registers(
+            &mut output,
+            registers,
+            true,
+            &support_map,
+            &mut all_register_names,
+            &mut all_aliases,
+        );
     }
 
     output.push_str(
@@ -151,17 +105,386 @@ fn main() {
 pub enum RegisterList {
 "#,
     );
-    for reg in all_register_names {
+    for reg in &all_register_names {
         output.push_str(&format!(
-            "\t{},\n",
-            uppercase_to_pascal_case(&reg.to_uppercase())
+            "{}\t{},\n",
+            beta_cfg(reg.beta),
+            uppercase_to_pascal_case(&reg.name.to_uppercase())
         ));
     }
-    output.push_str("}");
+    output.push('}');
+
+    generate_lookup(&mut output, &all_register_names, &all_aliases);
+
+    for (table, beta) in [("registers", false), ("registers_beta", true)] {
+        if let Some(registers) = data.get(table).and_then(|r| r.as_array()) {
+            for reg in registers {
+                if let Some(constants) = reg.get("constants").and_then(|c| c.as_array()) {
+                    let name = reg.get("name").unwrap().as_str().unwrap();
+                    let constants = constants
+                        .iter()
+                        .map(|constant| {
+                            (
+                                constant.get("name").unwrap().as_str().unwrap(),
+                                constant.get("value").unwrap().as_u64().unwrap(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+
+                    generate_constants(&mut output, name, &constants, beta);
+                }
+            }
+        }
+    }
+
+    if let Some(errors) = data.get("errors").and_then(|e| e.as_array()) {
+        let errors = errors
+            .iter()
+            .map(|error| ErrorCode {
+                // We cannot proceed if these properties do not exist, hence panic.
+                code: error.get("error").unwrap().as_u64().unwrap(),
+                name: error.get("string").unwrap().as_str().unwrap(),
+                desc: error.get("description").map(|v| v.as_str().unwrap()),
+            })
+            .collect::<Vec<_>>();
+
+        generate_errors(&mut output, &errors);
+    }
 
     // Write the generated code to the output file
     fs::write(&output_file, output).expect("Failed to write output file");
     println!("cargo:rerun-if-changed={}", input_file);
+    println!("cargo:rerun-if-env-changed={CONSTANTS_PATH_VAR}");
+    println!("cargo:rerun-if-changed=src/core/ranged_name.rs");
+}
+
+fn generate_registers(
+    output: &mut String,
+    registers: &[serde_json::Value],
+    beta: bool,
+    support_map: &SupportLookup,
+    all_register_names: &mut Vec<RegisterName>,
+    all_aliases: &mut Vec<RegisterAlias>,
+) {
+    for reg in registers {
+        // We cannot proceed if these properties do not exist, hence panic.
+        let name = reg.get("name").unwrap().as_str().unwrap();
+        let address = reg.get("address").unwrap().as_u64().unwrap();
+        let r#type = reg.get("type").unwrap().as_str().unwrap();
+        let desc = reg
+            .get("description")
+            .map(|v| v.as_str().unwrap())
+            .unwrap_or("No Description");
+        let default = reg.get("default").map(|v| v.as_f64().unwrap());
+
+        let flag = |key: &str| reg.get(key).map(|v| v.as_bool().unwrap()).unwrap_or(false);
+        let (streamable, is_buffer, uses_ram) =
+            (flag("streamable"), flag("isBuffer"), flag("usesRAM"));
+        let display_name = reg
+            .get("displayname")
+            .and_then(|names| names.as_array().unwrap().first())
+            .map(|name| name.as_str().unwrap());
+
+        let tags = reg
+            .get("tags")
+            .map(|tags| tags.as_array().unwrap().to_vec())
+            .unwrap_or(vec![])
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        let access_control =
+            crate::AccessControl::try_from(reg.get("readwrite").unwrap().as_str().unwrap())
+                .unwrap();
+
+        let all_devices = reg.get("devices").unwrap().as_array().unwrap();
+        let mut devices = vec![];
+        for device in all_devices {
+            devices.push(
+                crate::DeviceCompat::try_from(device)
+                    .unwrap_or_else(|_| panic!("Could not deserialise device note: {device:?}")),
+            );
+        }
+
+        let altnames = reg
+            .get("altnames")
+            .map(|names| names.as_array().unwrap().to_vec())
+            .unwrap_or(vec![])
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        let data_type = decode_type(r#type);
+        let expanded_names = expand_name(name);
+
+        // The documentation of the first tag which has any.
+        let doc_url = tags.iter().find_map(|tag| {
+            support_map
+                .hashmap
+                .get(tag)
+                .map(|suffix| format!("{}{}", support_map.base_url, suffix))
+        });
+
+        // Alternative names are ranged in step with the name they alias.
+        for altname in &altnames {
+            for ((alias, _), (name, _)) in expand_name(altname).into_iter().zip(&expanded_names) {
+                all_aliases.push(RegisterAlias {
+                    alias,
+                    name: name.clone(),
+                    beta,
+                });
+            }
+        }
+
+        for (expanded_name, offset) in &expanded_names {
+            all_register_names.push(RegisterName {
+                name: expanded_name.clone(),
+                address: address + (offset.unwrap_or(0) * size_of(data_type)),
+                beta,
+                streamable,
+                is_buffer,
+                uses_ram,
+                // A ranged display name numbers each register in place of the `#`.
+                display_name: display_name.map(|name| match offset {
+                    Some(i) => name.replace('#', &i.to_string()),
+                    None => name.to_string(),
+                }),
+                compatibility: format_compatibility(&devices),
+                tags: tags.clone(),
+                doc_url: doc_url.clone(),
+            });
+
+            generate_register(
+                output,
+                Register {
+                    data_type,
+                    name: expanded_name.as_str(),
+                    base_address: address,
+                    offset: *offset,
+                    access_control: &access_control,
+                    desc,
+                    devices: &devices,
+                    tags: &tags,
+                    default,
+                    beta,
+                },
+                support_map,
+            );
+        }
+
+        generate_family(
+            output,
+            name,
+            address,
+            &access_control,
+            data_type,
+            default,
+            beta,
+        );
+    }
+}
+
+/// Expands a ranged register name into each register it names, alongside its offset
+/// within the range. A malformed range fails the build, rather than being generated.
+fn expand_name(name: &str) -> Vec<(String, Option<u64>)> {
+    expand_register_name(name)
+        .unwrap_or_else(|| panic!("Register {name} has a malformed range"))
+        .into_iter()
+        .map(|(name, offset)| (name, offset.map(u64::from)))
+        .collect()
+}
+
+fn beta_cfg(beta: bool) -> String {
+    if beta {
+        format!("\t#[cfg(feature = \"{BETA_FEATURE}\")]\n")
+    } else {
+        String::new()
+    }
+}
+
+/// Generates the conversion from each `RegisterList` to its register, and the tables by
+/// which a register is found from its name or address. The tables are sorted here so that
+/// lookups may binary search them. Beta registers are kept in tables of their own, so
+/// their absence does not disturb the ordering of the rest.
+fn generate_lookup(output: &mut String, names: &[RegisterName], aliases: &[RegisterAlias]) {
+    output.push_str(
+        "\n\nimpl RegisterList {\n\
+         \t/// The register of this name.\n\
+         \tpub const fn register(&self) -> Register {\n\
+         \t\tmatch self {\n",
+    );
+    for reg in names {
+        output.push_str(&format!(
+            "{}\t\t\tRegisterList::{} => {}.register,\n",
+            beta_cfg(reg.beta),
+            uppercase_to_pascal_case(&reg.name),
+            reg.name.to_uppercase()
+        ));
+    }
+    output.push_str("\t\t}\n\t}\n\n");
+
+    output.push_str(
+        "\t/// The name of the register, as given by LabJack. For example, `AIN0`.\n\
+         \tpub const fn name(&self) -> &'static str {\n\
+         \t\tmatch self {\n",
+    );
+    for reg in names {
+        output.push_str(&format!(
+            "{}\t\t\tRegisterList::{} => {:?},\n",
+            beta_cfg(reg.beta),
+            uppercase_to_pascal_case(&reg.name),
+            reg.name
+        ));
+    }
+    output.push_str("\t\t}\n\t}\n\n");
+
+    output.push_str(
+        "\t/// The devices which support the register, and the firmware each requires.\n\
+         \tpub const fn compatibility(&self) -> &'static [DeviceCompatibility] {\n\
+         \t\tmatch self {\n",
+    );
+    for reg in names {
+        output.push_str(&format!(
+            "{}\t\t\tRegisterList::{} => {},\n",
+            beta_cfg(reg.beta),
+            uppercase_to_pascal_case(&reg.name),
+            reg.compatibility
+        ));
+    }
+    output.push_str("\t\t}\n\t}\n\n");
+
+    output.push_str(
+        "\t/// The tags grouping the register by subsystem, such as `AIN` or `DIO_EF`.\n\
+         \tpub const fn tags(&self) -> &'static [&'static str] {\n\
+         \t\tmatch self {\n",
+    );
+    for reg in names {
+        output.push_str(&format!(
+            "{}\t\t\tRegisterList::{} => &{:?},\n",
+            beta_cfg(reg.beta),
+            uppercase_to_pascal_case(&reg.name),
+            reg.tags
+        ));
+    }
+    output.push_str("\t\t}\n\t}\n\n");
+
+    output.push_str(
+        "\t/// The LabJack documentation of the register, from the first of its tags to have any.\n\
+         \tpub const fn doc_url(&self) -> Option<&'static str> {\n\
+         \t\tmatch self {\n",
+    );
+    for reg in names {
+        if reg.doc_url.is_none() {
+            continue;
+        }
+
+        output.push_str(&format!(
+            "{}\t\t\tRegisterList::{} => {:?},\n",
+            beta_cfg(reg.beta),
+            uppercase_to_pascal_case(&reg.name),
+            reg.doc_url
+        ));
+    }
+    output.push_str("\t\t\t_ => None,\n\t\t}\n\t}\n\n");
+
+    // Most registers carry none of the metadata, and so share the default.
+    output.push_str(
+        "\t/// The properties of the register beyond its address and type.\n\
+         \tpub fn metadata(&self) -> RegisterMetadata {\n\
+         \t\tmatch self {\n",
+    );
+    for reg in names {
+        if !reg.streamable && !reg.is_buffer && !reg.uses_ram && reg.display_name.is_none() {
+            continue;
+        }
+
+        output.push_str(&format!(
+            "{}\t\t\tRegisterList::{} => RegisterMetadata {{ streamable: {}, is_buffer: {}, uses_ram: {}, display_name: {:?} }},\n",
+            beta_cfg(reg.beta),
+            uppercase_to_pascal_case(&reg.name),
+            reg.streamable,
+            reg.is_buffer,
+            reg.uses_ram,
+            reg.display_name
+        ));
+    }
+    output.push_str("\t\t\t_ => RegisterMetadata::default(),\n\t\t}\n\t}\n}\n");
+
+    for (beta, prefix) in [(false, ""), (true, "BETA_")] {
+        let mut by_name = names
+            .iter()
+            .filter(|reg| reg.beta == beta)
+            .map(|reg| (reg.name.to_uppercase(), &reg.name))
+            .chain(
+                aliases
+                    .iter()
+                    .filter(|alias| alias.beta == beta)
+                    .map(|alias| (alias.alias.to_uppercase(), &alias.name)),
+            )
+            .collect::<Vec<_>>();
+        by_name.sort();
+
+        assert_unique(&by_name, "name");
+
+        let mut by_address = names
+            .iter()
+            .filter(|reg| reg.beta == beta)
+            .map(|reg| (reg.address, &reg.name))
+            .collect::<Vec<_>>();
+        by_address.sort();
+
+        assert_unique(&by_address, "address");
+
+        output.push_str(&format!(
+            "\n/// Each register name and alternative name, in upper case, sorted for lookup.\n\
+             {}pub(crate) static {prefix}REGISTER_NAMES: &[(&str, RegisterList)] = &[\n",
+            beta_cfg(beta).trim_start()
+        ));
+        for (key, name) in by_name {
+            output.push_str(&format!(
+                "\t({key:?}, RegisterList::{}),\n",
+                uppercase_to_pascal_case(name)
+            ));
+        }
+        output.push_str("];\n");
+
+        output.push_str(&format!(
+            "\n/// Each register, sorted by address for lookup.\n\
+             {}pub(crate) static {prefix}REGISTER_ADDRESSES: &[(u16, RegisterList)] = &[\n",
+            beta_cfg(beta).trim_start()
+        ));
+        for (address, name) in by_address {
+            output.push_str(&format!(
+                "\t({address}, RegisterList::{}),\n",
+                uppercase_to_pascal_case(name)
+            ));
+        }
+        output.push_str("];\n");
+
+        let mut by_tag = BTreeMap::<&str, Vec<&str>>::new();
+        for reg in names.iter().filter(|reg| reg.beta == beta) {
+            for tag in &reg.tags {
+                by_tag.entry(tag).or_default().push(&reg.name);
+            }
+        }
+
+        output.push_str(&format!(
+            "\n/// The registers of each tag, sorted by tag for lookup.\n\
+             {}pub(crate) static {prefix}REGISTER_TAGS: &[(&str, &[RegisterList])] = &[\n",
+            beta_cfg(beta).trim_start()
+        ));
+        for (tag, names) in by_tag {
+            output.push_str(&format!(
+                "\t({tag:?}, &[{}]),\n",
+                names
+                    .iter()
+                    .map(|name| format!("RegisterList::{}", uppercase_to_pascal_case(name)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        output.push_str("];\n");
+    }
 }
 
 fn decode_type(r#type: &str) -> &'static str {
@@ -171,7 +494,8 @@ fn decode_type(r#type: &str) -> &'static str {
         "UINT32" => "Uint32",
         "UINT64" => "Uint64",
         "FLOAT32" => "Float32",
-        "STRING" | "BYTE" => "Byte",
+        "STRING" => "String",
+        "BYTE" => "Byte",
         _ => panic!("Unsupported type {}", r#type),
     }
 }
@@ -184,6 +508,8 @@ fn size_of(data_type: &'static str) -> u64 {
         "Uint32" => 2,
         "Int32" => 2,
         "Uint64" => 4,
+        // LabJack strings are 50 bytes wide.
+        "String" => 25,
         _ => panic!("Unsupported type {}", data_type),
     }
 }
@@ -200,6 +526,112 @@ fn format_device_compat(compat: &DeviceCompat) -> String {
     )
 }
 
+/// Formats the devices supporting a register as a `DeviceCompatibility` slice.
+fn format_compatibility(devices: &[DeviceCompat]) -> String {
+    let devices = devices
+        .iter()
+        .map(|compat| {
+            format!(
+                "DeviceCompatibility {{ device: DeviceType::{}, min_firmware: {:?} }}",
+                compat.name,
+                compat.min_firmware.map(|v| v as f32)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("&[{}]", devices.join(", "))
+}
+
+/// The parameters of the `AccessLimitedRegister` for the given access and data type.
+fn access_limits(access_control: &AccessControl, data_type: &str) -> String {
+    let access = match access_control {
+        AccessControl::ReadOnly => "{ ReadableCtrl as u8 }",
+        AccessControl::ReadWrite => "{ AllCtrl as u8 }",
+        AccessControl::WriteOnly => "{ WritableCtrl as u8 }",
+    };
+
+    format!("{access}, {}", value_type(data_type))
+}
+
+/// The type-level `DataType` of the given data type.
+fn value_type(data_type: &str) -> &str {
+    match data_type {
+        "String" => "Str",
+        data_type => data_type,
+    }
+}
+
+/// Generates the accessor for a ranged register family, such as `ain(n)` for
+/// `AIN#(0:254)`, yielding the `n`th register of the range, if it exists. The address
+/// is computed from the base address and the stride of the register's type.
+fn generate_family(
+    output: &mut String,
+    name: &str,
+    base_address: u64,
+    access_control: &AccessControl,
+    data_type: &'static str,
+    default: Option<f64>,
+    beta: bool,
+) {
+    let Some(ranged) = RangedName::parse(name) else {
+        return;
+    };
+    let RangedName {
+        base,
+        first,
+        last,
+        suffix,
+    } = ranged;
+
+    let function = format!("{base}_{suffix}")
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase();
+
+    let (beta_cfg, table) = if beta {
+        (
+            format!("#[cfg(feature = \"{BETA_FEATURE}\")]\n"),
+            "BETA_REGISTER_ADDRESSES",
+        )
+    } else {
+        (String::new(), "REGISTER_ADDRESSES")
+    };
+
+    // Omits the identities of a base address of zero, or a stride of one.
+    let address = match (base_address, size_of(data_type)) {
+        (0, 1) => "n".to_string(),
+        (0, stride) => format!("n * {stride}"),
+        (base_address, 1) => format!("{base_address} + n"),
+        (base_address, stride) => format!("{base_address} + n * {stride}"),
+    };
+
+    output.push_str(&format!(
+        "\n/// The `n`th register of the `{name}` family, from `{}` to `{}`.\n\
+         /// Yields `None` where `n` is outside of the range `{first}..={last}`.\n\
+         {beta_cfg}pub fn {function}(n: u16) -> Option<AccessLimitedRegister<{}>> {{\n\
+         \tif !({first}..={last}).contains(&n) {{\n\
+         \t\treturn None;\n\
+         \t}}\n\n\
+         \tlet address = {address};\n\
+         \tSome(AccessLimitedRegister {{\n\
+         \t\tregister: Register {{\n\
+         \t\t\tname: crate::core::data_types::lookup({table}, &address)?,\n\
+         \t\t\taddress,\n\
+         \t\t\tdata_type: LabJackDataType::{data_type},\n\
+         \t\t\tdefault_value: {default:?},\n\
+         \t\t}},\n\
+         \t\tvalue_type: {},\n\
+         \t}})\n\
+         }}\n",
+        ranged.name(first).to_uppercase(),
+        ranged.name(last).to_uppercase(),
+        access_limits(access_control, data_type),
+        value_type(data_type),
+    ));
+}
+
 fn generate_register(
     output: &mut String,
     Register {
@@ -212,13 +644,21 @@ fn generate_register(
         devices,
         tags,
         default,
+        beta,
     }: Register,
     support_lookup: &SupportLookup,
 ) {
-    let control_value = match access_control {
-        AccessControl::ReadOnly => "{ ReadableCtrl as u8 }",
-        AccessControl::ReadWrite => "{ AllCtrl as u8 }",
-        AccessControl::WriteOnly => "{ WritableCtrl as u8 }",
+    let control_value = access_limits(access_control, data_type);
+
+    let (beta_note, beta_cfg) = if beta {
+        (
+            format!(
+                "  * **Beta**: Subject to change. Requires the `{BETA_FEATURE}` feature.\n  *\n"
+            ),
+            format!("#[cfg(feature = \"{BETA_FEATURE}\")]\n"),
+        )
+    } else {
+        (String::new(), String::new())
     };
 
     output.push_str(&format!(
@@ -226,7 +666,7 @@ fn generate_register(
 /**
   * #### {name}
   *
-  * **Access**: {access_control:?} \
+{beta_note}  * **Access**: {access_control:?} \
   * **Compatible Devices**:
 {}
   *
@@ -235,13 +675,14 @@ fn generate_register(
   * _Relevant Documentation:_
   * {}
   */
-pub const {}: AccessLimitedRegister<{control_value}> = AccessLimitedRegister {{
+{beta_cfg}pub const {}: AccessLimitedRegister<{control_value}> = AccessLimitedRegister {{
     register: Register {{
         name: RegisterList::{},
         address: {},
         data_type: LabJackDataType::{data_type},
         default_value: {default:?}
-    }}
+    }},
+    value_type: {},
 }};
 "#,
         devices
@@ -260,31 +701,151 @@ pub const {}: AccessLimitedRegister<{control_value}> = AccessLimitedRegister {{
             .collect::<Vec<_>>()
             .join(", "),
         name.to_uppercase(),
-        uppercase_to_pascal_case(&name),
+        uppercase_to_pascal_case(name),
         base_address + (offset.unwrap_or(0) * size_of(data_type)),
+        value_type(data_type),
     ));
 }
 
-// Parses a register name with a range and optional suffix, e.g., "AIN#(0:149)_EF_READ_C"
-fn parse_name_with_range_and_optional_suffix(
-    name: &str,
-) -> Option<(&str, std::ops::RangeInclusive<u64>, &str)> {
-    let range_start = name.find("#(")?;
-    let range_end = name.find(")")?;
-    let suffix_start = range_end + 1;
-
-    let base_name = &name[..range_start];
-    let range_part = &name[(range_start + 2)..range_end];
-    let suffix = name
-        .get(suffix_start..)
-        .unwrap_or("")
-        .trim_start_matches('_'); // Handle optional suffix
-
-    let mut parts = range_part.split(':');
-    let start: u64 = parts.next()?.parse().ok()?;
-    let end: u64 = parts.next()?.parse().ok()?;
-
-    Some((base_name, start..=end, suffix))
+fn generate_errors(output: &mut String, errors: &[ErrorCode]) {
+    output.push_str(
+        r#"
+
+/// An error code reported by a LabJack device, or by LJM.
+///
+/// Codes from 1200 to 1216 are Modbus exceptions, those from 1220 to 1399 belong to LJM,
+/// and those from 2000 onwards are raised by the device itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum LabJackErrorCode {
+"#,
+    );
+    for error in errors {
+        if let Some(desc) = error.desc.filter(|desc| !desc.is_empty()) {
+            output.push_str(&format!("\t/// {desc}\n"));
+        }
+        output.push_str(&format!(
+            "\t{} = {},\n",
+            uppercase_to_pascal_case(error.name),
+            error.code
+        ));
+    }
+    output.push_str("}\n\nimpl LabJackErrorCode {\n");
+
+    output.push_str(
+        "\t/// The code for the given value, if it is known.\n\
+         \tpub const fn from_code(code: u32) -> Option<LabJackErrorCode> {\n\
+         \t\tmatch code {\n",
+    );
+    for error in errors {
+        output.push_str(&format!(
+            "\t\t\t{} => Some(LabJackErrorCode::{}),\n",
+            error.code,
+            uppercase_to_pascal_case(error.name)
+        ));
+    }
+    output.push_str("\t\t\t_ => None,\n\t\t}\n\t}\n\n");
+
+    output.push_str(
+        "\t/// The name of the code, as given by LabJack. For example, `AIN_RANGE_INVALID`.\n\
+         \tpub const fn name(&self) -> &'static str {\n\
+         \t\tmatch self {\n",
+    );
+    for error in errors {
+        output.push_str(&format!(
+            "\t\t\tLabJackErrorCode::{} => {:?},\n",
+            uppercase_to_pascal_case(error.name),
+            error.name
+        ));
+    }
+    output.push_str("\t\t}\n\t}\n\n");
+
+    output.push_str(
+        "\t/// The description of the code, where LabJack provides one.\n\
+         \tpub const fn description(&self) -> Option<&'static str> {\n\
+         \t\tmatch self {\n",
+    );
+    for error in errors {
+        let desc = match error.desc.filter(|desc| !desc.is_empty()) {
+            Some(desc) => format!("Some({desc:?})"),
+            None => "None".to_string(),
+        };
+        output.push_str(&format!(
+            "\t\t\tLabJackErrorCode::{} => {desc},\n",
+            uppercase_to_pascal_case(error.name)
+        ));
+    }
+    output.push_str("\t\t}\n\t}\n}\n");
+}
+
+/// Generates an enum of the named values a register accepts, implementing `RegisterValue`
+/// for every register of the (possibly ranged) name. Unnamed values are preserved by the
+/// `Unknown` variant, as firmware may accept values the constants file does not list.
+fn generate_constants(output: &mut String, name: &str, constants: &[(&str, u64)], beta: bool) {
+    // A ranged name, such as `STREAM_OUT#(0:3)_ENABLE`, describes its values once for all.
+    let type_name = match RangedName::parse(name) {
+        Some(RangedName { base, suffix, .. }) => {
+            uppercase_to_pascal_case(&format!("{base}_{suffix}"))
+        }
+        None => uppercase_to_pascal_case(name),
+    };
+    let variant = |constant: &str| uppercase_to_pascal_case(&constant.replace(' ', "_"));
+    let cfg = beta_cfg(beta);
+    let cfg = cfg.trim_start();
+
+    output.push_str(&format!(
+        "\n/// The named values of the `{name}` register.\n\
+         {cfg}#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\n\
+         pub enum {type_name} {{\n"
+    ));
+    for (constant, value) in constants {
+        output.push_str(&format!(
+            "\t/// `{constant}`, a value of {value}.\n\t{},\n",
+            variant(constant)
+        ));
+    }
+    output.push_str("\t/// A value without a name.\n\tUnknown(u32),\n}\n");
+
+    output.push_str(&format!(
+        "\n{cfg}impl RegisterValue for {type_name} {{\n\
+         \tconst REGISTERS: &'static [RegisterList] = &[{}];\n\n\
+         \tfn from_value(value: u32) -> Self {{\n\
+         \t\tmatch value {{\n",
+        expand_name(name)
+            .iter()
+            .map(|(name, _)| format!("RegisterList::{}", uppercase_to_pascal_case(name)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    for (constant, value) in constants {
+        output.push_str(&format!(
+            "\t\t\t{value} => {type_name}::{},\n",
+            variant(constant)
+        ));
+    }
+    output.push_str(&format!(
+        "\t\t\tvalue => {type_name}::Unknown(value),\n\t\t}}\n\t}}\n\n\
+         \tfn value(&self) -> u32 {{\n\
+         \t\tmatch self {{\n"
+    ));
+    for (constant, value) in constants {
+        output.push_str(&format!(
+            "\t\t\t{type_name}::{} => {value},\n",
+            variant(constant)
+        ));
+    }
+    output.push_str(&format!(
+        "\t\t\t{type_name}::Unknown(value) => *value,\n\t\t}}\n\t}}\n}}\n"
+    ));
+}
+
+/// Lookups require each key of a sorted table to be unique.
+fn assert_unique<K: PartialEq + std::fmt::Display, V>(table: &[(K, V)], kind: &str) {
+    for pair in table.windows(2) {
+        if let [(a, _), (b, _)] = pair {
+            assert!(a != b, "Register {kind} {a} is not unique");
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -392,4 +953,33 @@ pub struct Register<'a> {
     pub devices: &'a Vec<DeviceCompat>,
     pub tags: &'a Vec<String>,
     pub default: Option<f64>,
+    pub beta: bool,
+}
+
+#[derive(Debug)]
+pub struct ErrorCode<'a> {
+    pub code: u64,
+    pub name: &'a str,
+    pub desc: Option<&'a str>,
+}
+
+#[derive(Debug)]
+pub struct RegisterName {
+    pub name: String,
+    pub address: u64,
+    pub beta: bool,
+    pub streamable: bool,
+    pub is_buffer: bool,
+    pub uses_ram: bool,
+    pub display_name: Option<String>,
+    pub compatibility: String,
+    pub tags: Vec<String>,
+    pub doc_url: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct RegisterAlias {
+    pub alias: String,
+    pub name: String,
+    pub beta: bool,
 }
@@ -1,3 +1,5 @@
 pub mod specific;
+pub mod strong;
 
 pub use specific::LabJackClient;
+pub use strong::StrongClient;
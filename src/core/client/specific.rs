@@ -1,14 +1,39 @@
 use crate::prelude::*;
 
 use either::Either;
+use log::debug;
+use std::time::{Instant, SystemTime};
 
-#[derive(Debug)]
+/// A connection to a LabJack device, through the transport `T`.
+///
+/// Where the transport is cloneable, so is the client, allowing many tasks to talk to
+/// the same device at once. For example, clones of a [`TcpTransport`] client share a
+/// single socket and pipeline their requests over it.
+///
+/// ```
+/// use labjack::prelude::*;
+///
+/// # async fn docs() {
+/// let device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+///     .await
+///     .expect("Must connect");
+///
+/// let mut logger = device.clone();
+/// let logging = tokio::spawn(async move { logger.read_register(*AIN0).await });
+///
+/// let mut control = device.clone();
+/// control.read_register(*AIN1).await.expect("Must read");
+/// logging.await.expect("Must join").expect("Must read");
+/// }
+/// ```
+#[derive(Debug, Clone)]
 pub struct LabJackClient<T>
 where
     T: Transport,
 {
     pub device: LabJackDevice,
     transport: T,
+    firmware: Option<f32>,
 }
 
 impl<T> LabJackClient<T>
@@ -16,11 +41,104 @@ where
     T: Transport,
 {
     pub fn new(device: LabJackDevice, transport: T) -> LabJackClient<T> {
-        LabJackClient { device, transport }
+        LabJackClient {
+            device,
+            transport,
+            firmware: None,
+        }
+    }
+
+    /// The firmware version of the device, once identified by [`LabJackClient::identify`].
+    pub fn firmware_version(&self) -> Option<f32> {
+        self.firmware
+    }
+
+    /// Reads the product id and firmware version of the device, such that registers it
+    /// does not support are rejected before a request is sent, see
+    /// [`Register::check_compatibility`]. This is done by [`LabJack::connect`] and the
+    /// like, so is only needed for clients constructed directly.
+    ///
+    /// Emulated devices are not identified, and so accept every register.
+    pub async fn identify(&mut self) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        if self.device.serial_number.is_emulated() {
+            return Ok(());
+        }
+
+        let values = self
+            .transport
+            .feedback(&[
+                FeedbackFunction::ReadRegister(*PRODUCT_ID),
+                FeedbackFunction::ReadRegister(*FIRMWARE_VERSION),
+            ])
+            .await
+            .map_err(Either::Right)?;
+
+        let [product, firmware] = values.as_slice() else {
+            return Err(Either::Left(Error::InvalidResponse));
+        };
+
+        // A device reporting an unrecognised product keeps the type it was located as.
+        let device_type = DeviceType::from(product.as_f64() as i32);
+        if matches!(
+            device_type,
+            DeviceType::T4 | DeviceType::T7 | DeviceType::T8 | DeviceType::DIGIT
+        ) {
+            self.device.device_type = device_type;
+        }
+
+        let firmware = firmware.as_f64() as f32;
+        self.firmware = (firmware > 0.0).then_some(firmware);
+
+        debug!(
+            "Identified {} running firmware {:?}",
+            self.device.device_type, self.firmware
+        );
+        Ok(())
+    }
+
+    /// Rejects any register the connected device does not support.
+    fn check(&self, registers: &[Register]) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        registers
+            .iter()
+            .try_for_each(|register| {
+                register.check_compatibility(self.device.device_type, self.firmware)
+            })
+            .map_err(Either::Left)
+    }
+
+    /// Rejects a mapped register which does not permit the `access`, or which the
+    /// connected device does not support.
+    fn check_mapped(
+        &self,
+        register: &MappedRegister,
+        access: AccessControl,
+    ) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        register
+            .check_access(access)
+            .and_then(|_| register.check_compatibility(self.device.device_type, self.firmware))
+            .map_err(Either::Left)
+    }
+
+    /// Wraps the client in a [`StrongClient`], which enforces the access
+    /// control of each register at compile time.
+    pub fn strong(self) -> StrongClient<T> {
+        StrongClient::new(self)
     }
 
-    /// Reads a singular value from a given address on the LabJack.
-    pub async fn read<An, Ctx>(
+    /// The transport used to communicate with the device.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Allows the transport to be configured after connecting, such
+    /// as setting the [`TcpTransport::set_timeout`] of a request.
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Reads a register, converting its value through the `channel`, such as a
+    /// thermocouple converting a voltage to a temperature.
+    pub async fn read_with<An, Ctx>(
         &mut self,
         address: Register,
         channel: An,
@@ -33,29 +151,448 @@ where
         Ok(channel.to_digital(context, value))
     }
 
+    /// Reads a register as a [`Reading`], recording when it was read and how long it took.
+    ///
+    /// `STRING` registers have no numeric value, and so are refused with
+    /// [`Reason::DecodingError`]. They may instead be read through
+    /// [`LabJackClient::read_reading_with`], keeping the raw value.
+    pub async fn read_reading(
+        &mut self,
+        register: Register,
+    ) -> Result<Reading, Either<Error, <T as Transport>::Error>> {
+        numeric(&[register])?;
+        let reading = self.read_reading_with(register, (), ()).await?;
+        Ok(reading.map(|raw| raw.as_f64()))
+    }
+
+    /// Reads a register as a [`Reading`], converting the value through the `channel`, as
+    /// with [`LabJackClient::read_with`].
+    pub async fn read_reading_with<An, Ctx>(
+        &mut self,
+        register: Register,
+        channel: An,
+        context: Ctx,
+    ) -> Result<Reading<<An as Adc<Ctx>>::Digital>, Either<Error, <T as Transport>::Error>>
+    where
+        An: Adc<Ctx>,
+    {
+        let timestamp = SystemTime::now();
+        let started = Instant::now();
+        let raw = self.read_register(register).await?;
+
+        Ok(Reading {
+            serial_number: self.device.serial_number,
+            register: register.name,
+            raw,
+            value: channel.to_digital(context, raw),
+            timestamp,
+            latency: started.elapsed(),
+        })
+    }
+
+    /// Reads a set of adjacent registers in a single transaction, as with
+    /// [`LabJackClient::read_registers`], yielding a [`Reading`] of each. As with
+    /// [`LabJackClient::read_reading`], `STRING` registers are refused.
+    pub async fn read_readings(
+        &mut self,
+        registers: &[Register],
+    ) -> Result<Vec<Reading>, Either<Error, <T as Transport>::Error>> {
+        numeric(registers)?;
+        let timestamp = SystemTime::now();
+        let started = Instant::now();
+        let values = self.read_registers(registers).await?;
+        let latency = started.elapsed();
+
+        Ok(registers
+            .iter()
+            .zip(values)
+            .map(|(register, raw)| Reading {
+                serial_number: self.device.serial_number,
+                register: register.name,
+                raw,
+                value: raw.as_f64(),
+                timestamp,
+                latency,
+            })
+            .collect())
+    }
+
     pub async fn read_register(
         &mut self,
         address: Register,
     ) -> Result<LabJackDataValue, Either<Error, <T as Transport>::Error>> {
+        self.check(&[address])?;
         self.transport
             .read_register(address)
             .await
             .map_err(Either::Right)
     }
+
+    pub async fn write_register(
+        &mut self,
+        address: Register,
+        value: LabJackDataValue,
+    ) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        self.check(&[address])?;
+        self.transport
+            .write_register(address, value)
+            .await
+            .map_err(Either::Right)
+    }
+
+    /// Reads a register as one of its named values, see [`RegisterValue`].
+    pub async fn read_value<V>(
+        &mut self,
+        register: Register,
+    ) -> Result<V, Either<Error, <T as Transport>::Error>>
+    where
+        V: RegisterValue,
+    {
+        let value = self.read_register(register).await?;
+        V::decode(register, value).map_err(Either::Left)
+    }
+
+    /// Writes one of the named values of a register, see [`RegisterValue`].
+    pub async fn write_value<V>(
+        &mut self,
+        register: Register,
+        value: V,
+    ) -> Result<(), Either<Error, <T as Transport>::Error>>
+    where
+        V: RegisterValue,
+    {
+        let value = value.encode(register).map_err(Either::Left)?;
+        self.write_register(register, value).await
+    }
+
+    /// Reads a register as the primitive of its data type, such as an `f32` from `AIN0`.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// # async fn docs() {
+    /// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+    ///     .await
+    ///     .expect("Must connect");
+    ///
+    /// let voltage: f32 = device.read(AIN0).await.expect("Must read");
+    /// # }
+    /// ```
+    ///
+    /// Registers found at runtime, such as through [`Register::by_name`], carry no data type
+    /// at the type level, and so are instead read through [`LabJackClient::read_register`].
+    pub async fn read<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+    ) -> Result<D::Value, Either<Error, <T as Transport>::Error>>
+    where
+        D: Decode,
+    {
+        let value = self.read_register(register.register).await?;
+        register.value_type.decode(value).map_err(Either::Left)
+    }
+
+    /// Writes the primitive of a register's data type, such as a `u16` to `FIO0`.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// # async fn docs() {
+    /// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+    ///     .await
+    ///     .expect("Must connect");
+    ///
+    /// device.write(FIO0, 1u16).await.expect("Must write");
+    /// # }
+    /// ```
+    ///
+    /// A value of any other type is refused at compile time.
+    ///
+    /// ```compile_fail
+    /// use labjack::prelude::*;
+    ///
+    /// # async fn docs() {
+    /// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+    ///     .await
+    ///     .expect("Must connect");
+    ///
+    /// device.write(FIO0, 1.0f32).await.expect("Must write");
+    /// # }
+    /// ```
+    pub async fn write<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+        value: D::Value,
+    ) -> Result<(), Either<Error, <T as Transport>::Error>>
+    where
+        D: Coerce,
+    {
+        let value = register.value_type.coerce(value);
+        self.write_register(register.register, value).await
+    }
+
+    /// Closes the connection to the device, once every request in flight has completed.
+    ///
+    /// Clones of this client share the connection, and so are closed with it.
+    pub async fn close(mut self) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        self.transport.close().await.map_err(Either::Right)
+    }
+
+    /// Reads a set of adjacent registers in a single transaction, yielding one value per register.
+    ///
+    /// The registers must directly follow each other in address, such as `AIN0` through `AIN13`.
+    pub async fn read_registers(
+        &mut self,
+        registers: &[Register],
+    ) -> Result<Vec<LabJackDataValue>, Either<Error, <T as Transport>::Error>> {
+        self.check(registers)?;
+        let function = ReadBlockFunction::new(registers).map_err(Either::Left)?;
+
+        self.transport
+            .read_block(function)
+            .await
+            .map_err(Either::Right)
+    }
+
+    /// Writes a value to each of a set of adjacent registers in a single transaction.
+    pub async fn write_registers(
+        &mut self,
+        registers: &[Register],
+        values: &[LabJackDataValue],
+    ) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        self.check(registers)?;
+        let function = WriteBlockFunction::new(registers, values).map_err(Either::Left)?;
+
+        self.transport
+            .write_block(function)
+            .await
+            .map_err(Either::Right)
+    }
+
+    /// Reads the value at an address as the given data type. This reaches registers which
+    /// this crate was not built with, such as those introduced by a newer constants file
+    /// loaded as a [`RegisterMap`].
+    ///
+    /// As nothing is known of the register, it is not checked against the connected device.
+    pub async fn read_address(
+        &mut self,
+        address: Address,
+        data_type: LabJackDataType,
+    ) -> Result<LabJackDataValue, Either<Error, <T as Transport>::Error>> {
+        let function = ReadBlockFunction::at(address, data_type).map_err(Either::Left)?;
+
+        let values = self
+            .transport
+            .read_block(function)
+            .await
+            .map_err(Either::Right)?;
+
+        values
+            .into_iter()
+            .next()
+            .ok_or(Either::Left(Error::InvalidResponse))
+    }
+
+    /// Writes a value to an address, coerced to the given data type, as with
+    /// [`LabJackClient::read_address`].
+    pub async fn write_address(
+        &mut self,
+        address: Address,
+        data_type: LabJackDataType,
+        value: LabJackDataValue,
+    ) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        let function = WriteBlockFunction::at(address, data_type, value).map_err(Either::Left)?;
+
+        self.transport
+            .write_block(function)
+            .await
+            .map_err(Either::Right)
+    }
+
+    /// Reads a register of a [`RegisterMap`] loaded at runtime, see
+    /// [`LabJackClient::read_address`]. Registers which may only be written, or which the
+    /// connected device does not support, are rejected before a request is sent.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// # async fn docs(map: RegisterMap) {
+    /// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+    ///     .await
+    ///     .expect("Must connect");
+    ///
+    /// let register = map.by_name("NEW_FEATURE").expect("Must exist");
+    /// let value = device.read_mapped(register).await.expect("Must read");
+    /// # }
+    /// ```
+    pub async fn read_mapped(
+        &mut self,
+        register: &MappedRegister,
+    ) -> Result<LabJackDataValue, Either<Error, <T as Transport>::Error>> {
+        self.check_mapped(register, AccessControl::ReadableCtrl)?;
+        self.read_address(register.address, register.data_type)
+            .await
+    }
+
+    /// Writes a register of a [`RegisterMap`] loaded at runtime, see
+    /// [`LabJackClient::write_address`]. Registers which may only be read, or which the
+    /// connected device does not support, are rejected before a request is sent.
+    pub async fn write_mapped(
+        &mut self,
+        register: &MappedRegister,
+        value: LabJackDataValue,
+    ) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        self.check_mapped(register, AccessControl::WritableCtrl)?;
+        self.write_address(register.address, register.data_type, value)
+            .await
+    }
+
+    /// Reads `len` bytes from a buffer register, such as `I2C_DATA_RX`, by repeatedly
+    /// reading its address. Reads too long for one packet are split across several.
+    ///
+    /// As the device advances the buffer with each read, no other request should read the
+    /// same buffer until this has completed.
+    pub async fn read_buffer(
+        &mut self,
+        register: Register,
+        len: usize,
+    ) -> Result<Vec<u8>, Either<Error, <T as Transport>::Error>> {
+        self.check(&[register])?;
+        let functions = ReadBlockFunction::buffer(register, len).map_err(Either::Left)?;
+
+        let mut bytes = Vec::with_capacity(len);
+        for function in functions {
+            let words = self
+                .transport
+                .read_block(function)
+                .await
+                .map_err(Either::Right)?;
+
+            for word in words {
+                match (word, register.buffer_width()) {
+                    (LabJackDataValue::Uint16(word), Some(2)) => {
+                        bytes.extend(word.to_be_bytes());
+                    }
+                    (LabJackDataValue::Uint16(word), _) => bytes
+                        .push(u8::try_from(word).map_err(|_| {
+                            Either::Left(Error::InvalidData(Reason::DecodingError))
+                        })?),
+                    _ => return Err(Either::Left(Error::InvalidData(Reason::DecodingError))),
+                }
+            }
+        }
+
+        // A buffer packing two bytes per register may yield one more than requested.
+        bytes.truncate(len);
+        Ok(bytes)
+    }
+
+    /// Writes `bytes` into a buffer register, such as `I2C_DATA_TX`, by repeatedly writing
+    /// its address. Writes too long for one packet are split across several.
+    pub async fn write_buffer(
+        &mut self,
+        register: Register,
+        bytes: &[u8],
+    ) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        self.check(&[register])?;
+
+        for function in WriteBlockFunction::buffer(register, bytes).map_err(Either::Left)? {
+            self.transport
+                .write_block(function)
+                .await
+                .map_err(Either::Right)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads `count` values from a buffer register of words, such as `USER_RAM_FIFO0_DATA_U16`,
+    /// each as the register's data type. Buffers of bytes are instead read through
+    /// [`LabJackClient::read_buffer`].
+    pub async fn read_buffer_values(
+        &mut self,
+        register: Register,
+        count: usize,
+    ) -> Result<Vec<LabJackDataValue>, Either<Error, <T as Transport>::Error>> {
+        self.check(&[register])?;
+
+        let mut values = Vec::with_capacity(count);
+        for function in ReadBlockFunction::buffer_values(register, count).map_err(Either::Left)? {
+            values.extend(
+                self.transport
+                    .read_block(function)
+                    .await
+                    .map_err(Either::Right)?,
+            );
+        }
+
+        Ok(values)
+    }
+
+    /// Writes `values` into a buffer register of words, such as `STREAM_OUT0_BUFFER_U16`,
+    /// each coerced to the register's data type.
+    pub async fn write_buffer_values(
+        &mut self,
+        register: Register,
+        values: &[LabJackDataValue],
+    ) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        self.check(&[register])?;
+
+        for function in WriteBlockFunction::buffer_values(register, values).map_err(Either::Left)? {
+            self.transport
+                .write_block(function)
+                .await
+                .map_err(Either::Right)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads and writes a set of registers in as few round trips as possible, using
+    /// Modbus Feedback. Yields one value per [`FeedbackFunction::ReadRegister`], in order.
+    pub async fn feedback(
+        &mut self,
+        functions: &[FeedbackFunction],
+    ) -> Result<Vec<LabJackDataValue>, Either<Error, <T as Transport>::Error>> {
+        for function in functions {
+            match function {
+                FeedbackFunction::ReadRegister(register)
+                | FeedbackFunction::WriteRegister(register, _) => self.check(&[*register])?,
+            }
+        }
+
+        self.transport
+            .feedback(functions)
+            .await
+            .map_err(Either::Right)
+    }
+}
+
+/// Rejects registers whose values cannot be converted to an `f64`, such that every
+/// [`Reading`] holds a number which survives serialisation.
+fn numeric<E>(registers: &[Register]) -> Result<(), Either<Error, E>> {
+    match registers
+        .iter()
+        .any(|register| register.data_type == LabJackDataType::String)
+    {
+        true => Err(Either::Left(Error::InvalidData(Reason::DecodingError))),
+        false => Ok(()),
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
+    use either::Either;
 
     /// A mocked DAQ used to override the values
     /// provided by conversions to test how the unit value operates.
     struct ButtEnd(LabJackDataValue);
 
-    impl Adc for ButtEnd {
+    impl Adc<()> for ButtEnd {
         type Digital = LabJackDataValue;
 
-        fn to_digital(&self, _voltage: LabJackDataValue) -> Self::Digital {
+        fn to_digital(&self, _context: (), _voltage: LabJackDataValue) -> Self::Digital {
             self.0
         }
     }
@@ -75,7 +612,7 @@ mod test {
             .expect("Must connect");
 
         let end = ButtEnd(LabJackDataValue::Uint16(100));
-        let value = device.read(*AIN55, end).await;
+        let value = device.read_with(*AIN55, end, ()).await;
 
         assert!(value.is_ok(), "result={:?}", value);
 
@@ -89,7 +626,7 @@ mod test {
             .await
             .expect("Must connect");
 
-        let value = device.read(*AIN55, ()).await;
+        let value = device.read_with(*AIN55, (), ()).await;
 
         assert!(value.is_ok(), "result={:?}", value);
 
@@ -108,11 +645,94 @@ mod test {
     }
 
     #[tokio::test]
-    async fn read_many() {
+    async fn feedback_read_and_write() {
         let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
             .await
             .expect("Must connect");
 
+        let values = device
+            .feedback(&[
+                FeedbackFunction::WriteRegister(*TEST_UINT32, LabJackDataValue::Uint32(7)),
+                FeedbackFunction::ReadRegister(*AIN55),
+                FeedbackFunction::ReadRegister(*TEST_UINT32),
+            ])
+            .await
+            .expect("Must perform feedback");
+
+        assert_eq!(
+            values,
+            vec![LabJackDataValue::Float32(0.0), LabJackDataValue::Uint32(7)]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_and_write_registers() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        device
+            .write_registers(
+                &[*DAC0, *DAC1],
+                &[
+                    LabJackDataValue::Float32(1.5),
+                    LabJackDataValue::Float32(2.5),
+                ],
+            )
+            .await
+            .expect("Must write");
+
+        let values = device
+            .read_registers(&[*DAC0, *DAC1])
+            .await
+            .expect("Must read");
+
+        assert_eq!(
+            values,
+            vec![
+                LabJackDataValue::Float32(1.5),
+                LabJackDataValue::Float32(2.5)
+            ]
+        );
+
+        let gap = device.read_registers(&[*AIN0, *AIN2]).await;
+        assert!(matches!(gap, Err(either::Either::Left(..))));
+    }
+
+    #[tokio::test]
+    async fn clones_share_device() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+        let mut handle = device.clone();
+
+        let writer = tokio::spawn(async move {
+            handle
+                .write_registers(&[*TEST_UINT32], &[LabJackDataValue::Uint32(42)])
+                .await
+                .expect("Must write");
+        });
+        writer.await.expect("Writer task must complete");
+
+        let value = device.read_register(*TEST_UINT32).await.expect("Must read");
+        assert_eq!(value, LabJackDataValue::Uint32(42));
+    }
+
+    #[tokio::test]
+    async fn close_client() {
+        let device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        device.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn read_many() {
+        let _device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
         // Static-Typing will only go so far.
         //
         // See the below where we can aggregate on registers with a common data type.
@@ -141,7 +761,7 @@ mod test {
         let registers = vec![*AIN55, *AIN56];
 
         for register in registers.into_iter() {
-            let value = device.read(register, ()).await.expect("!");
+            let value = device.read_with(register, (), ()).await.expect("!");
             println!("{:?}", value);
 
             // But if we needed to unionise the values
@@ -152,4 +772,466 @@ mod test {
             println!("AsF64={}", value.as_f64())
         }
     }
+
+    #[tokio::test]
+    async fn rejects_incompatible_registers() {
+        let ip = std::net::IpAddr::from([127, 0, 0, 1]);
+        let device = LabJackDevice::known(ip, DeviceType::TSERIES, 470000000);
+        let mut transport = Emulated::connect(device).await.expect("Must connect");
+
+        transport
+            .write_register(*PRODUCT_ID, LabJackDataValue::Float32(7.0))
+            .await
+            .expect("Must write");
+        transport
+            .write_register(*FIRMWARE_VERSION, LabJackDataValue::Float32(1.01))
+            .await
+            .expect("Must write");
+
+        let mut client = LabJackClient::new(device, transport);
+        client.identify().await.expect("Must identify");
+        assert_eq!(client.device.device_type, DeviceType::T7);
+        assert_eq!(client.firmware_version(), Some(1.01));
+
+        client.read_register(*AIN0).await.expect("Must read");
+
+        // Requires firmware 1.0224 on the T7.
+        let read = client.read_register(*POWER_MODE).await;
+        assert!(matches!(
+            read,
+            Err(Either::Left(Error::Incompatible(
+                Incompatibility::Firmware { .. }
+            )))
+        ));
+
+        // Only available on the T4.
+        let read = client
+            .feedback(&[FeedbackFunction::ReadRegister(*DIO_ANALOG_ENABLE)])
+            .await;
+        assert!(matches!(
+            read,
+            Err(Either::Left(Error::Incompatible(
+                Incompatibility::Device { .. }
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_and_write_strings() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        let name = LabJackString::try_from("Bench T7").expect("Must fit");
+        device
+            .write_register(*DEVICE_NAME_DEFAULT, LabJackDataValue::String(name))
+            .await
+            .expect("Must write");
+
+        let value = device
+            .read_register(*DEVICE_NAME_DEFAULT)
+            .await
+            .expect("Must read");
+        assert_eq!(value, LabJackDataValue::String(name));
+        assert_eq!(name.to_string(), "Bench T7");
+        assert_eq!(DEVICE_NAME_DEFAULT.data_type.size(), 25);
+    }
+
+    #[tokio::test]
+    async fn read_and_write_typed() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        device.write(DAC0, 2.5).await.expect("Must write");
+        let voltage: f32 = device.read(DAC0).await.expect("Must read");
+        assert_eq!(voltage, 2.5);
+
+        device.write(TEST_UINT32, 7).await.expect("Must write");
+        assert_eq!(device.read(TEST_UINT32).await.expect("Must read"), 7u32);
+
+        let name = LabJackString::try_from("Bench T7").expect("Must fit");
+        device
+            .write(DEVICE_NAME_DEFAULT, name)
+            .await
+            .expect("Must write");
+        let read: LabJackString = device.read(DEVICE_NAME_DEFAULT).await.expect("Must read");
+        assert_eq!(read, name);
+
+        // The untyped path remains for registers found at runtime.
+        let register = Register::by_name("DAC0").expect("Must exist");
+        let value = device.read_register(register).await.expect("Must read");
+        assert_eq!(value, LabJackDataValue::Float32(2.5));
+    }
+
+    #[tokio::test]
+    async fn read_and_write_mapped() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        let map = RegisterMap::from_json(
+            r#"{
+                "header": { "version": "2099.01.01.A" },
+                "registers": [
+                    { "address": 61990, "name": "NEW_FEATURE", "type": "UINT32", "devices": ["T7"], "readwrite": "RW" }
+                ]
+            }"#,
+        )
+        .expect("Must parse");
+
+        // The register is only known to the loaded constants.
+        let register = map.by_name("NEW_FEATURE").expect("Must exist");
+        assert!(register.register().is_none());
+
+        device
+            .write_mapped(register, LabJackDataValue::Uint16(7))
+            .await
+            .expect("Must write");
+        assert_eq!(
+            device.read_mapped(register).await.expect("Must read"),
+            LabJackDataValue::Uint32(7)
+        );
+
+        assert!(matches!(
+            device.read_address(u16::MAX, LabJackDataType::Uint32).await,
+            Err(Either::Left(Error::InvalidData(
+                Reason::NonContiguousRegisters
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_incompatible_mapped() {
+        let ip = std::net::IpAddr::from([127, 0, 0, 1]);
+        let device = LabJackDevice::known(ip, DeviceType::T7, 470000000);
+        let transport = Emulated::connect(device).await.expect("Must connect");
+        let mut client = LabJackClient::new(device, transport);
+
+        let map = RegisterMap::from_json(
+            r#"{
+                "header": { "version": "2099.01.01.A" },
+                "registers": [
+                    { "address": 61990, "name": "NEW_STATUS", "type": "UINT32", "devices": ["T7"], "readwrite": "R" },
+                    { "address": 61992, "name": "NEW_T4_FEATURE", "type": "UINT32", "devices": ["T4"], "readwrite": "RW" }
+                ]
+            }"#,
+        )
+        .expect("Must parse");
+
+        let status = map.by_name("NEW_STATUS").expect("Must exist");
+        client.read_mapped(status).await.expect("Must read");
+        assert!(matches!(
+            client
+                .write_mapped(status, LabJackDataValue::Uint32(1))
+                .await,
+            Err(Either::Left(Error::Incompatible(
+                Incompatibility::Access { .. }
+            )))
+        ));
+
+        let feature = map.by_name("NEW_T4_FEATURE").expect("Must exist");
+        assert!(matches!(
+            client.read_mapped(feature).await,
+            Err(Either::Left(Error::Incompatible(
+                Incompatibility::Device { .. }
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_and_write_buffers() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::{TcpListener, TcpStream};
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Must bind to a port");
+        let address = listener.local_addr().expect("Must have an address");
+
+        let device = async move {
+            let (mut stream, _) = listener.accept().await.expect("Must accept");
+
+            // Three bytes of I2C_DATA_RX span two registers, both read from its address.
+            let mut request = [0u8; 12];
+            stream.read_exact(&mut request).await.expect("Must read");
+            assert_eq!(request[7..], [0x03, 0x14, 0x28, 0x00, 0x02]);
+            stream
+                .write_all(&[0, 1, 0, 0, 0, 7, 1, 0x03, 4, 0x01, 0x02, 0x03, 0x00])
+                .await
+                .expect("Must write");
+
+            // ASYNCH_DATA_TX carries one byte in the low half of each register.
+            let mut request = [0u8; 17];
+            stream.read_exact(&mut request).await.expect("Must read");
+            assert_eq!(
+                request[7..],
+                [0x10, 0x15, 0x72, 0x00, 0x02, 0x04, 0x00, 0xAB, 0x00, 0xCD]
+            );
+            stream
+                .write_all(&[0, 2, 0, 0, 0, 6, 1, 0x10, 0x15, 0x72, 0x00, 0x02])
+                .await
+                .expect("Must write");
+        };
+
+        let client = async move {
+            let stream = TcpStream::connect(address).await.expect("Must connect");
+            let device = LabJackDevice::known(address.ip(), DeviceType::TSERIES, 470000000);
+            let mut client = LabJackClient::new(device, TcpTransport::new(stream));
+
+            let bytes = client
+                .read_buffer(*I2C_DATA_RX, 3)
+                .await
+                .expect("Must read");
+            assert_eq!(bytes, vec![0x01, 0x02, 0x03]);
+
+            client
+                .write_buffer(*ASYNCH_DATA_TX, &[0xAB, 0xCD])
+                .await
+                .expect("Must write");
+
+            let written = client.write_buffer(*DAC0, &[0x01]).await;
+            assert!(matches!(
+                written,
+                Err(Either::Left(Error::InvalidData(Reason::NotABuffer)))
+            ));
+        };
+
+        tokio::join!(device, client);
+    }
+
+    /// Answers `requests` block reads and writes as a loopback, such that each write appends
+    /// its registers to a single queue, from which each read takes the oldest.
+    async fn loopback_device(listener: tokio::net::TcpListener, requests: usize) {
+        use std::collections::VecDeque;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (mut stream, _) = listener.accept().await.expect("Must accept");
+        let mut queue = VecDeque::new();
+
+        for _ in 0..requests {
+            let mut header = [0u8; 7];
+            stream.read_exact(&mut header).await.expect("Must read");
+            let mut body = vec![0u8; u16::from_be_bytes([header[4], header[5]]) as usize - 1];
+            stream.read_exact(&mut body).await.expect("Must read");
+
+            let response = match body[0] {
+                0x10 => {
+                    queue.extend(body[6..].iter().copied());
+                    body[..5].to_vec()
+                }
+                0x03 => {
+                    let bytes = 2 * u16::from_be_bytes([body[3], body[4]]) as usize;
+                    let mut response = vec![0x03, bytes as u8];
+                    response.extend(queue.drain(..bytes));
+                    response
+                }
+                code => panic!("Unexpected function {code}"),
+            };
+
+            let mut reply = header[..4].to_vec();
+            reply.extend((response.len() as u16 + 1).to_be_bytes());
+            reply.push(header[6]);
+            reply.extend(response);
+            stream.write_all(&reply).await.expect("Must write");
+        }
+    }
+
+    #[tokio::test]
+    async fn buffers_round_trip() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Must bind to a port");
+        let address = listener.local_addr().expect("Must have an address");
+        let device = loopback_device(listener, 6);
+
+        let client = async move {
+            let stream = tokio::net::TcpStream::connect(address)
+                .await
+                .expect("Must connect");
+            let device = LabJackDevice::known(address.ip(), DeviceType::TSERIES, 470000000);
+            let mut client = LabJackClient::new(device, TcpTransport::new(stream));
+
+            // Each word of a UINT16 buffer is a whole value, not a byte.
+            let words = [
+                LabJackDataValue::Uint16(0x1234),
+                LabJackDataValue::Uint16(0xABCD),
+            ];
+            client
+                .write_buffer_values(*USER_RAM_FIFO0_DATA_U16, &words)
+                .await
+                .expect("Must write");
+            let read = client
+                .read_buffer_values(*USER_RAM_FIFO0_DATA_U16, 2)
+                .await
+                .expect("Must read");
+            assert_eq!(read, words);
+
+            let values = [
+                LabJackDataValue::Uint32(0x00112233),
+                LabJackDataValue::Uint32(7),
+            ];
+            client
+                .write_buffer_values(*USER_RAM_FIFO0_DATA_U32, &values)
+                .await
+                .expect("Must write");
+            let read = client
+                .read_buffer_values(*USER_RAM_FIFO0_DATA_U32, 2)
+                .await
+                .expect("Must read");
+            assert_eq!(read, values);
+
+            // A BYTE buffer packs two bytes into each register, padding an odd number.
+            client
+                .write_buffer(*SPI_DATA_TX, &[0x01, 0x02, 0x03])
+                .await
+                .expect("Must write");
+            let read = client
+                .read_buffer(*SPI_DATA_RX, 3)
+                .await
+                .expect("Must read");
+            assert_eq!(read, vec![0x01, 0x02, 0x03]);
+
+            // Buffers of words are not buffers of bytes.
+            for register in [*USER_RAM_FIFO0_DATA_U16, *STREAM_OUT0_BUFFER_F32] {
+                assert!(matches!(
+                    client.write_buffer(register, &[0x01]).await,
+                    Err(Either::Left(Error::InvalidData(Reason::NotAByteBuffer)))
+                ));
+            }
+            assert!(matches!(
+                client.read_buffer_values(*AIN0, 1).await,
+                Err(Either::Left(Error::InvalidData(Reason::NotABuffer)))
+            ));
+        };
+
+        tokio::join!(device, client);
+    }
+
+    #[tokio::test]
+    async fn readings_record_provenance() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        device
+            .write_registers(
+                &[*DAC0, *DAC1],
+                &[
+                    LabJackDataValue::Float32(1.5),
+                    LabJackDataValue::Float32(2.5),
+                ],
+            )
+            .await
+            .expect("Must write");
+
+        let reading = device.read_reading(*DAC0).await.expect("Must read");
+        assert_eq!(reading.serial_number, LabJackSerialNumber::emulated());
+        assert_eq!(reading.register, RegisterList::Dac0);
+        assert_eq!(reading.raw, LabJackDataValue::Float32(1.5));
+        assert_eq!(reading.value, 1.5);
+
+        let converted = device
+            .read_reading_with(*DAC1, ButtEnd(LabJackDataValue::Uint16(7)), ())
+            .await
+            .expect("Must read");
+        assert_eq!(converted.raw, LabJackDataValue::Float32(2.5));
+        assert_eq!(converted.value, LabJackDataValue::Uint16(7));
+
+        let readings = device
+            .read_readings(&[*DAC0, *DAC1])
+            .await
+            .expect("Must read");
+        assert_eq!(
+            readings.iter().map(|r| r.register).collect::<Vec<_>>(),
+            vec![RegisterList::Dac0, RegisterList::Dac1]
+        );
+        assert!(readings
+            .iter()
+            .all(|r| r.timestamp == readings[0].timestamp));
+    }
+
+    #[test]
+    fn readings_round_trip_serde() {
+        let reading = Reading {
+            serial_number: LabJackSerialNumber(470012345),
+            register: RegisterList::Ain0,
+            raw: LabJackDataValue::Float32(3.3),
+            value: 3.3f32 as f64,
+            timestamp: std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1),
+            latency: std::time::Duration::from_micros(850),
+        };
+
+        let json = serde_json::to_string(&reading).expect("Must serialise");
+        let decoded: Reading = serde_json::from_str(&json).expect("Must deserialise");
+        assert_eq!(decoded, reading);
+
+        let ip = std::net::IpAddr::from([192, 168, 1, 25]);
+        let device = LabJackDevice::known(ip, DeviceType::T7, 470012345);
+        let json = serde_json::to_string(&device).expect("Must serialise");
+        let decoded: LabJackDevice = serde_json::from_str(&json).expect("Must deserialise");
+        assert_eq!(decoded, device);
+    }
+
+    #[tokio::test]
+    async fn string_readings_round_trip_serde() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        let name = LabJackString::try_from("Bench T7").expect("Must fit");
+        device
+            .write_register(*DEVICE_NAME_DEFAULT, LabJackDataValue::String(name))
+            .await
+            .expect("Must write");
+
+        // A string has no numeric value, so is only read with its raw value kept.
+        assert!(matches!(
+            device.read_reading(*DEVICE_NAME_DEFAULT).await,
+            Err(Either::Left(Error::InvalidData(Reason::DecodingError)))
+        ));
+
+        let reading = device
+            .read_reading_with(*DEVICE_NAME_DEFAULT, (), ())
+            .await
+            .expect("Must read");
+        assert_eq!(reading.value, LabJackDataValue::String(name));
+
+        let json = serde_json::to_string(&reading).expect("Must serialise");
+        let decoded: Reading<LabJackDataValue> =
+            serde_json::from_str(&json).expect("Must deserialise");
+        assert_eq!(decoded, reading);
+    }
+
+    #[tokio::test]
+    async fn read_and_write_values() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect");
+
+        device
+            .write_value(*STREAM_OUT1_ENABLE, StreamOutEnable::Enabled)
+            .await
+            .expect("Must write");
+        let value: StreamOutEnable = device
+            .read_value(*STREAM_OUT1_ENABLE)
+            .await
+            .expect("Must read");
+        assert_eq!(value, StreamOutEnable::Enabled);
+
+        // Values without a name are preserved.
+        device
+            .write_register(*WIFI_STATUS, LabJackDataValue::Uint32(2999))
+            .await
+            .expect("Must write");
+        let value: WifiStatus = device.read_value(*WIFI_STATUS).await.expect("Must read");
+        assert_eq!(value, WifiStatus::Unknown(2999));
+
+        // Values are only accepted by their own registers.
+        let written = device.write_value(*DIO_STATE, LedComm::On).await;
+        assert!(matches!(
+            written,
+            Err(Either::Left(Error::InvalidData(Reason::RegisterMismatch)))
+        ));
+    }
 }
@@ -0,0 +1,206 @@
+use crate::prelude::*;
+
+use either::Either;
+
+/// A [`LabJackClient`] which only accepts the generated, access-limited registers, such
+/// that reading a write-only register, or writing a read-only one, fails to compile.
+/// Values are read and written as the primitive of each register's data type.
+///
+/// ```
+/// use labjack::prelude::*;
+///
+/// # async fn docs() {
+/// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+///     .await
+///     .expect("Must connect")
+///     .strong();
+///
+/// let serial: u32 = device.read(SERIAL_NUMBER).await.expect("Must read");
+/// device.write(DAC0, 2.5).await.expect("Must write");
+/// # }
+/// ```
+///
+/// The serial number of a device may only be read,
+///
+/// ```compile_fail
+/// use labjack::prelude::*;
+///
+/// # async fn docs() {
+/// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+///     .await
+///     .expect("Must connect")
+///     .strong();
+///
+/// device.write(SERIAL_NUMBER, 470000000).await.expect("Must write");
+/// # }
+/// ```
+///
+/// whereas a reboot may only be written.
+///
+/// ```compile_fail
+/// use labjack::prelude::*;
+///
+/// # async fn docs() {
+/// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+///     .await
+///     .expect("Must connect")
+///     .strong();
+///
+/// device.read(SYSTEM_REBOOT).await.expect("Must read");
+/// # }
+/// ```
+///
+/// Registers without access control, such as those found by [`Register::by_name`], are
+/// used through the inner client, see [`StrongClient::client`].
+#[derive(Debug, Clone)]
+pub struct StrongClient<T>
+where
+    T: Transport,
+{
+    client: LabJackClient<T>,
+}
+
+impl<T> StrongClient<T>
+where
+    T: Transport,
+{
+    pub fn new(client: LabJackClient<T>) -> StrongClient<T> {
+        StrongClient { client }
+    }
+
+    /// The client through which any register may be used, without access control.
+    pub fn client(&mut self) -> &mut LabJackClient<T> {
+        &mut self.client
+    }
+
+    pub fn into_inner(self) -> LabJackClient<T> {
+        self.client
+    }
+
+    /// Reads a readable register as the primitive of its data type.
+    pub async fn read<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+    ) -> Result<D::Value, Either<Error, <T as Transport>::Error>>
+    where
+        AccessLimitedRegister<ACCESS, D>: Readable,
+        D: Decode,
+    {
+        self.client.read(register).await
+    }
+
+    /// Writes the primitive of a writable register's data type.
+    pub async fn write<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+        value: D::Value,
+    ) -> Result<(), Either<Error, <T as Transport>::Error>>
+    where
+        AccessLimitedRegister<ACCESS, D>: Writable,
+        D: Coerce,
+    {
+        self.client.write(register, value).await
+    }
+
+    /// Reads `len` bytes from a readable buffer register, see [`LabJackClient::read_buffer`].
+    pub async fn read_buffer<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+        len: usize,
+    ) -> Result<Vec<u8>, Either<Error, <T as Transport>::Error>>
+    where
+        AccessLimitedRegister<ACCESS, D>: Readable,
+    {
+        self.client.read_buffer(register.register, len).await
+    }
+
+    /// Writes `bytes` into a writable buffer register, see [`LabJackClient::write_buffer`].
+    pub async fn write_buffer<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+        bytes: &[u8],
+    ) -> Result<(), Either<Error, <T as Transport>::Error>>
+    where
+        AccessLimitedRegister<ACCESS, D>: Writable,
+    {
+        self.client.write_buffer(register.register, bytes).await
+    }
+
+    /// Reads `count` values from a readable buffer register of words, see
+    /// [`LabJackClient::read_buffer_values`].
+    pub async fn read_buffer_values<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+        count: usize,
+    ) -> Result<Vec<LabJackDataValue>, Either<Error, <T as Transport>::Error>>
+    where
+        AccessLimitedRegister<ACCESS, D>: Readable,
+    {
+        self.client
+            .read_buffer_values(register.register, count)
+            .await
+    }
+
+    /// Writes `values` into a writable buffer register of words, see
+    /// [`LabJackClient::write_buffer_values`].
+    pub async fn write_buffer_values<const ACCESS: u8, D>(
+        &mut self,
+        register: AccessLimitedRegister<ACCESS, D>,
+        values: &[LabJackDataValue],
+    ) -> Result<(), Either<Error, <T as Transport>::Error>>
+    where
+        AccessLimitedRegister<ACCESS, D>: Writable,
+    {
+        self.client
+            .write_buffer_values(register.register, values)
+            .await
+    }
+
+    /// Closes the connection to the device, see [`LabJackClient::close`].
+    pub async fn close(self) -> Result<(), Either<Error, <T as Transport>::Error>> {
+        self.client.close().await
+    }
+}
+
+impl<T> From<LabJackClient<T>> for StrongClient<T>
+where
+    T: Transport,
+{
+    fn from(client: LabJackClient<T>) -> Self {
+        StrongClient::new(client)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[tokio::test]
+    async fn read_and_write() {
+        let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+            .await
+            .expect("Must connect")
+            .strong();
+
+        device.write(DAC0, 1.25).await.expect("Must write");
+        assert_eq!(device.read(DAC0).await.expect("Must read"), 1.25);
+
+        device
+            .write(SYSTEM_REBOOT, 0x4C4A0000)
+            .await
+            .expect("Must write");
+        let serial: u32 = device.read(SERIAL_NUMBER).await.expect("Must read");
+        assert_eq!(serial, 0);
+
+        // Registers without access control remain usable through the inner client.
+        let register = Register::by_name("DAC0").expect("Must exist");
+        let value = device
+            .client()
+            .read_register(register)
+            .await
+            .expect("Must read");
+        assert_eq!(value, LabJackDataValue::Float32(1.25));
+
+        device.close().await.expect("Must close");
+    }
+}
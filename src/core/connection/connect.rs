@@ -1,5 +1,5 @@
 use crate::core::modbus::Error;
-use crate::core::LabJackDevice;
+use crate::core::{ConnectOptions, LabJackDevice};
 use crate::prelude::modbus::Transport;
 use std::io::Sink;
 
@@ -10,6 +10,15 @@ pub trait Connect {
         device: LabJackDevice,
     ) -> impl std::future::Future<Output = Result<Self::Transport, Error>> + Send;
 
+    /// Connects according to the given [`ConnectOptions`]. By default, the options
+    /// are ignored, which suits transports that do not connect over a network.
+    fn connect_with_options(
+        device: LabJackDevice,
+        _options: ConnectOptions,
+    ) -> impl std::future::Future<Output = Result<Self::Transport, Error>> + Send {
+        Self::connect(device)
+    }
+
     fn sink() -> Result<Sink, Error> {
         todo!()
     }
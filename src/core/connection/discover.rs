@@ -62,7 +62,7 @@ impl Discover {
 
         // Collect all devices from the UDP broadcast
         Ok(std::iter::from_fn(move || {
-            let mut buf = vec![0u8; expected_bytes];
+            let mut buf = vec![0u8; MODBUS_HEADER_SIZE + 1 + expected_bytes];
             match broadcast.recv_from(&mut buf) {
                 Ok((size, addr)) => {
                     debug!("Some LabJack Found! PacketSize={}, Addr={}", size, addr);
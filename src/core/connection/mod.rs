@@ -1,5 +1,9 @@
 pub mod connect;
 pub mod discover;
+pub mod options;
+pub mod state;
 
 pub use connect::*;
 pub use discover::*;
+pub use options::*;
+pub use state::*;
@@ -0,0 +1,92 @@
+use crate::prelude::*;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// The default duration allowed to establish a connection to a device.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Determines how a transport connects to its device, for use with
+/// [`LabJack::connect_with_options`].
+///
+/// Options which have no meaning for a transport are ignored by it. For example,
+/// [`Udp`] establishes no session, and so has no use for the `connect_timeout`.
+///
+/// ```
+/// use std::time::Duration;
+/// use labjack::prelude::*;
+///
+/// // Reach the device through a port-forwarder, keeping the idle connection alive.
+/// let options = ConnectOptions {
+///     port: Some(5020),
+///     keepalive: Some(Duration::from_secs(30)),
+///     ..ConnectOptions::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConnectOptions {
+    /// The port to connect to, in place of the [`LabJackDevice::port`].
+    pub port: Option<u16>,
+    /// The duration allowed to establish the connection, after which it fails with
+    /// [`Error::Timeout`]. If `None`, the operating system's own timeout applies.
+    pub connect_timeout: Option<Duration>,
+    /// Whether to disable Nagle's algorithm (`TCP_NODELAY`), sending each request
+    /// without waiting to coalesce it with the next.
+    pub nodelay: bool,
+    /// The idle time before TCP keepalive probes are sent, and the interval between
+    /// them. If `None`, keepalive is left disabled.
+    pub keepalive: Option<Duration>,
+    /// The local address to bind to before connecting. If `None`, it is chosen
+    /// by the operating system.
+    pub local_address: Option<SocketAddr>,
+    /// The Modbus unit id placed in each request. Only of use when the device sits
+    /// behind a gateway which bridges on it; LabJack devices themselves ignore it.
+    pub unit_id: u8,
+    /// How the connection is re-established should it be lost. Only of use to [`Tcp`],
+    /// as [`Udp`] holds no connection to lose.
+    pub backoff: Backoff,
+}
+
+impl ConnectOptions {
+    /// The address of the `device`, accounting for any overriding `port`.
+    pub fn address(&self, device: &LabJackDevice) -> SocketAddr {
+        SocketAddr::new(device.ip_address, self.port.unwrap_or(device.port))
+    }
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        ConnectOptions {
+            port: None,
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            nodelay: false,
+            keepalive: None,
+            local_address: None,
+            unit_id: BASE_UNIT_ID,
+            backoff: Backoff::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn port_overrides_device() {
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 25));
+        let device = LabJackDevice {
+            port: 5020,
+            ..LabJackDevice::known(ip, DeviceType::T7, 470000000)
+        };
+
+        let options = ConnectOptions::default();
+        assert_eq!(options.address(&device), SocketAddr::new(ip, 5020));
+
+        let options = ConnectOptions {
+            port: Some(1502),
+            ..ConnectOptions::default()
+        };
+        assert_eq!(options.address(&device), SocketAddr::new(ip, 1502));
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// The state of the connection between a transport and its device.
+///
+/// Transports which maintain a connection, such as [`TcpTransport`], publish
+/// each change of state over a [`tokio::sync::watch`] channel, allowing a
+/// supervising task to react to the device dropping off the network.
+///
+/// [`TcpTransport`]: crate::prelude::TcpTransport
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The connection is established and requests may be made.
+    Connected,
+    /// The connection was lost. Requests fail until it is re-established.
+    Reconnecting,
+    /// The connection has ended, and will not be re-established.
+    Closed,
+}
+
+impl Display for ConnectionState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ConnectionState::Connected => "CONNECTED",
+            ConnectionState::Reconnecting => "RECONNECTING",
+            ConnectionState::Closed => "CLOSED",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+/// Determines how a lost connection is re-established. The delay before each
+/// attempt doubles from the `initial_delay`, up to the `max_delay`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Backoff {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// The number of attempts made before the connection is [`ConnectionState::Closed`].
+    /// If `None`, attempts continue indefinitely.
+    pub max_attempts: Option<usize>,
+}
+
+impl Backoff {
+    /// A policy which never attempts to reconnect.
+    pub fn never() -> Backoff {
+        Backoff {
+            max_attempts: Some(0),
+            ..Backoff::default()
+        }
+    }
+
+    /// The delay before the given attempt, counting from zero.
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.try_into().unwrap_or(u32::MAX));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+
+    /// Whether another attempt may be made after `attempts` have failed.
+    pub fn permits(&self, attempts: usize) -> bool {
+        self.max_attempts.is_none_or(|max| attempts < max)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            max_attempts: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn delay_doubles_until_max() {
+        let backoff = Backoff {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            max_attempts: None,
+        };
+
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay(1), Duration::from_millis(200));
+        assert_eq!(backoff.delay(2), Duration::from_millis(400));
+        assert_eq!(backoff.delay(3), Duration::from_millis(500));
+        assert_eq!(backoff.delay(usize::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn attempts_are_bounded() {
+        assert!(Backoff::default().permits(usize::MAX));
+        assert!(!Backoff::never().permits(0));
+
+        let backoff = Backoff {
+            max_attempts: Some(2),
+            ..Backoff::default()
+        };
+        assert!(backoff.permits(1));
+        assert!(!backoff.permits(2));
+    }
+}
@@ -10,7 +10,7 @@ pub trait Adc<Ctx> {
 impl Adc<()> for () {
     type Digital = LabJackDataValue;
 
-    fn to_digital(&self, context: (), voltage: LabJackDataValue) -> Self::Digital {
+    fn to_digital(&self, _context: (), voltage: LabJackDataValue) -> Self::Digital {
         voltage
     }
 }
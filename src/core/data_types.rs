@@ -2,7 +2,9 @@ use crate::prelude::*;
 
 use num::traits::ToBytes;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::ops::Deref;
+use std::str::FromStr;
 
 macro_rules! impl_traits {
     ($($struct:ident => $value:ty),* $(,)?) => {
@@ -16,6 +18,15 @@ macro_rules! impl_traits {
                 }
             }
 
+            impl Decode for $struct {
+                fn decode(&self, value: LabJackDataValue) -> Result<<$struct as DataType>::Value, Error> {
+                    match value {
+                        LabJackDataValue::$struct(value) => Ok(value),
+                        _ => Err(Error::InvalidData(Reason::DecodingError)),
+                    }
+                }
+            }
+
             impl DataType for $struct {
                 type Value = $value;
 
@@ -37,6 +48,12 @@ pub trait Coerce: DataType {
     fn coerce(&self, value: <Self as DataType>::Value) -> LabJackDataValue; // Must not fail, ever.
 }
 
+/// Allows for downcasting a [`LabJackDataValue`] into the primitive of a data type,
+/// the inverse of [`Coerce`]. Fails where the value is of another type.
+pub trait Decode: Coerce {
+    fn decode(&self, value: LabJackDataValue) -> Result<<Self as DataType>::Value, Error>;
+}
+
 impl_traits! {
     Uint16 => u16,
     Uint32 => u32,
@@ -46,14 +63,41 @@ impl_traits! {
     Byte => u8,
 }
 
+/// The data type of a `STRING` register, whose values are a [`LabJackString`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Str;
+
+impl Coerce for Str {
+    fn coerce(&self, value: LabJackString) -> LabJackDataValue {
+        LabJackDataValue::String(value)
+    }
+}
+
+impl Decode for Str {
+    fn decode(&self, value: LabJackDataValue) -> Result<LabJackString, Error> {
+        match value {
+            LabJackDataValue::String(value) => Ok(value),
+            _ => Err(Error::InvalidData(Reason::DecodingError)),
+        }
+    }
+}
+
+impl DataType for Str {
+    type Value = LabJackString;
+
+    fn data_type(&self) -> LabJackDataType {
+        LabJackDataType::String
+    }
+
+    fn bytes(&self, value: &LabJackString) -> Vec<u8> {
+        value.bytes().to_vec()
+    }
+}
+
 pub trait Decoder {
     fn decode_as(&self, r#type: LabJackDataType) -> Result<LabJackDataValue, Error>;
 }
 
-// pub trait Decode: Coerce {
-//     fn try_decode(&self, v: &dyn Decoder) -> Result<<Self as DataType>::Value, Error>;
-// }
-
 pub struct StandardDecoder<'a> {
     pub bytes: &'a [u8],
 }
@@ -76,6 +120,7 @@ impl Decoder for StandardDecoder<'_> {
 
 /// Defines the ability for a register to be written or read from
 /// with the compile-time constraints of an access-control layer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
 pub enum AccessControl {
     AllCtrl = 1,
@@ -90,11 +135,16 @@ pub enum AccessControl {
 /// This means, when using the [`StrongClient`], it enforces
 /// invariants in access control over registers with separate
 /// reading and writing privileges.
-pub struct AccessLimitedRegister<const ACCESS_CONTROL: u8> {
+///
+/// The [`DataType`] of the register is carried as `T`, such that its
+/// values may be read and written as their primitive, see
+/// [`LabJackClient::read`].
+pub struct AccessLimitedRegister<const ACCESS_CONTROL: u8, T> {
     pub register: Register,
+    pub value_type: T,
 }
 
-impl<const N: u8> Deref for AccessLimitedRegister<N> {
+impl<const N: u8, T> Deref for AccessLimitedRegister<N, T> {
     type Target = Register;
 
     fn deref(&self) -> &Self::Target {
@@ -102,11 +152,11 @@ impl<const N: u8> Deref for AccessLimitedRegister<N> {
     }
 }
 
-impl<const N: u8> AccessLimitedRegister<N> {
+impl<const N: u8, T> AccessLimitedRegister<N, T> {
     /// Allows the consuming application to safely unravel an ACL
     /// locked register into its inner [`Register`]. This is useful
     /// for using the underlying traits
-    fn register(self) -> Register {
+    pub fn register(self) -> Register {
         self.register
     }
 }
@@ -119,15 +169,241 @@ pub struct Register {
     pub default_value: Option<f64>,
 }
 
-trait Readable {}
-trait Writable {}
+/// The properties of a register beyond its address and type, as given by LabJack.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct RegisterMetadata {
+    /// Whether the register may be included in a stream scan list.
+    pub streamable: bool,
+    /// Whether the register is a buffer, whose successive reads or writes at the same
+    /// address stream through its contents, rather than repeating one value.
+    pub is_buffer: bool,
+    /// Whether the register is held in the device's limited RAM.
+    pub uses_ram: bool,
+    /// A human-readable name, such as `Analog In 3` for `AIN3`.
+    pub display_name: Option<&'static str>,
+}
+
+/// A device which supports a register, from the given firmware version onwards.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DeviceCompatibility {
+    pub device: DeviceType,
+    /// The earliest firmware with support. If `None`, every version is supported.
+    pub min_firmware: Option<f32>,
+}
+
+impl Register {
+    /// Checks the register may be used on the `device`, running the given `firmware`
+    /// version, if known. Devices of an unknown or emulated type are not checked.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// assert!(POWER_MODE.check_compatibility(DeviceType::T7, Some(1.0224)).is_ok());
+    /// assert!(POWER_MODE.check_compatibility(DeviceType::T7, Some(1.0100)).is_err());
+    /// assert!(POWER_MODE.check_compatibility(DeviceType::T4, None).is_err());
+    /// ```
+    pub fn check_compatibility(
+        &self,
+        device: DeviceType,
+        firmware: Option<f32>,
+    ) -> Result<(), Error> {
+        check_compatibility(
+            self.name.name(),
+            self.name.compatibility(),
+            device,
+            firmware,
+        )
+    }
+
+    /// The properties of the register beyond its address and type.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// let metadata = AIN3.metadata();
+    /// assert!(metadata.streamable);
+    /// assert_eq!(metadata.display_name, Some("Analog In 3"));
+    /// ```
+    pub fn metadata(&self) -> RegisterMetadata {
+        self.name.metadata()
+    }
+
+    /// The number of bytes of a buffer register carried by each Modbus register it spans.
+    /// A `BYTE` buffer, such as `I2C_DATA_TX`, packs two bytes into each, whereas the
+    /// `ASYNCH_DATA_TX` and `ASYNCH_DATA_RX` buffers carry one byte in the low half of each.
+    ///
+    /// Registers which are not buffers of bytes have no width. Buffers of other values,
+    /// such as `USER_RAM_FIFO0_DATA_U16`, hold whole words instead.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// assert_eq!(I2C_DATA_TX.buffer_width(), Some(2));
+    /// assert_eq!(ASYNCH_DATA_TX.buffer_width(), Some(1));
+    /// assert_eq!(USER_RAM_FIFO0_DATA_U16.buffer_width(), None);
+    /// ```
+    pub fn buffer_width(&self) -> Option<usize> {
+        if !self.metadata().is_buffer {
+            return None;
+        }
+
+        match (self.data_type, self.name) {
+            (LabJackDataType::Byte, _) => Some(2),
+            (_, RegisterList::AsynchDataTx | RegisterList::AsynchDataRx) => Some(1),
+            _ => None,
+        }
+    }
+
+    /// The tags grouping the register by subsystem, such as `AIN` or `DIO_EF`.
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.name.tags()
+    }
+
+    /// A link to the section of the LabJack documentation describing the register.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// assert!(AIN0.tags().contains(&"AIN"));
+    /// assert!(AIN0.doc_url().is_some_and(|url| url.starts_with("https://")));
+    /// ```
+    pub fn doc_url(&self) -> Option<&'static str> {
+        self.name.doc_url()
+    }
+
+    /// Finds the register with the given name, or alternative name, ignoring case.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// let register = Register::by_name("DIO0").expect("Must exist");
+    /// assert_eq!(register.name, RegisterList::Fio0);
+    /// assert_eq!(register.address, FIO0.address);
+    /// ```
+    pub fn by_name(name: &str) -> Option<Register> {
+        let name = name.to_ascii_uppercase();
+        let found = lookup(REGISTER_NAMES, &name.as_str());
+
+        #[cfg(feature = "beta-registers")]
+        let found = found.or_else(|| lookup(BETA_REGISTER_NAMES, &name.as_str()));
+
+        found.map(|name| name.register())
+    }
+
+    /// Finds the register which starts at the given address.
+    pub fn by_address(address: Address) -> Option<Register> {
+        let found = lookup(REGISTER_ADDRESSES, &address);
+
+        #[cfg(feature = "beta-registers")]
+        let found = found.or_else(|| lookup(BETA_REGISTER_ADDRESSES, &address));
+
+        found.map(|name| name.register())
+    }
+}
+
+/// Checks a register with the given `compatibility` may be used on the `device`, running
+/// the given `firmware` version, if known. Devices of an unknown or emulated type are not checked.
+pub(crate) fn check_compatibility(
+    register: impl Into<Cow<'static, str>>,
+    compatibility: &[DeviceCompatibility],
+    device: DeviceType,
+    firmware: Option<f32>,
+) -> Result<(), Error> {
+    if !matches!(
+        device,
+        DeviceType::T4 | DeviceType::T7 | DeviceType::T8 | DeviceType::DIGIT
+    ) {
+        return Ok(());
+    }
+
+    let Some(compatibility) = compatibility
+        .iter()
+        .find(|compatibility| compatibility.device == device)
+    else {
+        return Err(Error::Incompatible(Incompatibility::Device {
+            register: register.into(),
+            device,
+        }));
+    };
+
+    match (compatibility.min_firmware, firmware) {
+        (Some(required), Some(installed)) if installed < required => {
+            Err(Error::Incompatible(Incompatibility::Firmware {
+                register: register.into(),
+                device,
+                required,
+                installed,
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Every register with the given tag, ignoring case, such that registers may be grouped
+/// by subsystem.
+///
+/// ```
+/// use labjack::prelude::*;
+///
+/// let extended = registers_with_tag("ain_ef").collect::<Vec<_>>();
+/// assert!(extended.iter().any(|register| register.name == RegisterList::Ain0EfIndex));
+/// assert!(extended.iter().all(|register| register.tags().contains(&"AIN_EF")));
+/// ```
+pub fn registers_with_tag(tag: &str) -> impl Iterator<Item = Register> {
+    let tag = tag.to_ascii_uppercase();
+    let found = lookup(REGISTER_TAGS, &tag.as_str())
+        .unwrap_or_default()
+        .iter();
+
+    #[cfg(feature = "beta-registers")]
+    let found = found.chain(lookup(BETA_REGISTER_TAGS, &tag.as_str()).unwrap_or_default());
+
+    found.map(RegisterList::register)
+}
+
+/// Searches one of the generated lookup tables, which are sorted by key.
+pub(crate) fn lookup<K: Ord, V: Copy>(table: &[(K, V)], key: &K) -> Option<V> {
+    let index = table.binary_search_by(|(k, _)| k.cmp(key)).ok()?;
+    table.get(index).map(|(_, value)| *value)
+}
+
+impl FromStr for RegisterList {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Register::from_str(s).map(|register| register.name)
+    }
+}
+
+impl FromStr for Register {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Register::by_name(s)
+            .ok_or_else(|| Error::InvalidData(Reason::UnknownRegister(s.to_string())))
+    }
+}
+
+mod sealed {
+    /// Only the access-limited registers of this crate may be [`Readable`](super::Readable)
+    /// or [`Writable`](super::Writable), as their access is given by LabJack.
+    pub trait Sealed {}
+
+    impl<const N: u8, T> Sealed for super::AccessLimitedRegister<N, T> {}
+}
+
+/// A register which the device allows to be read, as required by [`StrongClient::read`].
+pub trait Readable: sealed::Sealed {}
+
+/// A register which the device allows to be written, as required by [`StrongClient::write`].
+pub trait Writable: sealed::Sealed {}
 
 use AccessControl::*;
 
-impl Writable for AccessLimitedRegister<{ WritableCtrl as u8 }> {}
-impl Readable for AccessLimitedRegister<{ ReadableCtrl as u8 }> {}
-impl Writable for AccessLimitedRegister<{ AllCtrl as u8 }> {}
-impl Readable for AccessLimitedRegister<{ AllCtrl as u8 }> {}
+impl<T> Writable for AccessLimitedRegister<{ WritableCtrl as u8 }, T> {}
+impl<T> Readable for AccessLimitedRegister<{ ReadableCtrl as u8 }, T> {}
+impl<T> Writable for AccessLimitedRegister<{ AllCtrl as u8 }, T> {}
+impl<T> Readable for AccessLimitedRegister<{ AllCtrl as u8 }, T> {}
 
 // pub trait __RegisterTrait {
 //     fn entity(&self) -> LabJackEntity;
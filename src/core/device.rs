@@ -34,7 +34,7 @@ impl Deref for LabJackSerialNumber {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct LabJackDevice {
     pub device_type: DeviceType,
     pub connection_type: ConnectionType,
@@ -33,12 +33,16 @@ use crate::prelude::*;
 /// to use to connect to a device.
 ///
 /// - [`Tcp`].
-///     Used to connect over Ethernet. Wi-Fi is supported over this measure but is not recommended.
-///     See the [`MAX_DATA_LENGTH`] for why.
+///   Used to connect over Ethernet. Wi-Fi is supported over this measure but is not recommended.
+///   See the [`MAX_DATA_LENGTH`] for why.
+///
+/// - [`Udp`].
+///   Used to connect over Ethernet without establishing a session. Requests which go
+///   unanswered are retransmitted, see the [`UdpTransport`] for details.
 ///
 /// - [`Emulated`].
-///     Allows for testing behaviour without a device present. Similar to the [Demo Mode](https://support.labjack.com/docs/open-ljm-user-s-guide#Open[LJMUser'sGuide]-Identifier[in]) connection.
-///     Therefore, does not require a device present. Not fully-featured, but can be used for unit and integration testing.
+///   Allows for testing behaviour without a device present. Similar to the [Demo Mode](https://support.labjack.com/docs/open-ljm-user-s-guide#Open[LJMUser'sGuide]-Identifier[in]) connection.
+///   Therefore, does not require a device present. Not fully-featured, but can be used for unit and integration testing.
 ///
 ///  > Notice there is no `Usb` transport. This is not yet supported. You are welcome to contribute if you require this feature.
 ///
@@ -114,6 +118,7 @@ impl LabJack {
     ) -> Result<LabJackClient<<T as Connect>::Transport>, Error>
     where
         T: Connect,
+        Error: From<<T::Transport as Transport>::Error>,
     {
         let serial = id.into();
         let device = if serial.is_emulated() {
@@ -123,7 +128,7 @@ impl LabJack {
         };
 
         let transport = T::connect(device).await?;
-        Ok(LabJackClient::new(device, transport))
+        LabJack::identify(LabJackClient::new(device, transport)).await
     }
 
     /// Connects to a device using the specified transport, given a device has already been located.
@@ -176,8 +181,58 @@ impl LabJack {
     ) -> Result<LabJackClient<<T as Connect>::Transport>, Error>
     where
         T: Connect,
+        Error: From<<T::Transport as Transport>::Error>,
     {
         let transport = T::connect(device).await?;
-        Ok(LabJackClient::new(device, transport))
+        LabJack::identify(LabJackClient::new(device, transport)).await
+    }
+
+    /// Connects to an already located device, as with [`LabJack::connect_with`], according
+    /// to the given [`ConnectOptions`]. This allows reaching a device through a port-forwarder
+    /// or Modbus gateway, and tuning the connection itself.
+    ///
+    /// ```
+    /// use std::net::IpAddr;
+    /// use std::str::FromStr;
+    /// use std::time::Duration;
+    /// use labjack::prelude::*;
+    ///
+    /// # async fn docs() {
+    /// let known_ip = IpAddr::from_str("192.168.1.25").expect("Must resolve");
+    /// let known_device = LabJackDevice::known(known_ip, DeviceType::TSERIES, 470000000);
+    ///
+    /// let options = ConnectOptions {
+    ///     port: Some(5020),
+    ///     connect_timeout: Some(Duration::from_secs(1)),
+    ///     nodelay: true,
+    ///     ..ConnectOptions::default()
+    /// };
+    ///
+    /// let connected = LabJack::connect_with_options::<Tcp>(known_device, options).await;
+    /// println!("Connected to known device {:?}", connected);
+    /// }
+    /// ```
+    pub async fn connect_with_options<T>(
+        device: LabJackDevice,
+        options: ConnectOptions,
+    ) -> Result<LabJackClient<<T as Connect>::Transport>, Error>
+    where
+        T: Connect,
+        Error: From<<T::Transport as Transport>::Error>,
+    {
+        let transport = T::connect_with_options(device, options).await?;
+        LabJack::identify(LabJackClient::new(device, transport)).await
+    }
+
+    async fn identify<T>(mut client: LabJackClient<T>) -> Result<LabJackClient<T>, Error>
+    where
+        T: Transport,
+        Error: From<T::Error>,
+    {
+        client
+            .identify()
+            .await
+            .map_err(|err| err.either(|err| err, Error::from))?;
+        Ok(client)
     }
 }
@@ -1,8 +1,7 @@
 use crate::prelude::*;
 
-use num::{FromPrimitive, ToPrimitive};
-use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Debug, Display, Formatter};
 
 #[repr(u32)]
 #[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
@@ -17,7 +16,7 @@ pub enum LabJackDataType {
 }
 
 pub trait DataType: Debug {
-    type Value: FromPrimitive + ToPrimitive + Clone + Debug;
+    type Value: Clone + Debug;
 
     fn data_type(&self) -> LabJackDataType;
     fn bytes(&self, value: &Self::Value) -> Vec<u8>;
@@ -37,6 +36,7 @@ impl LabJackDataType {
         match self {
             LabJackDataType::Byte | LabJackDataType::Uint16 => 1,
             LabJackDataType::Uint64 => 4,
+            LabJackDataType::String => (LABJACK_STRING_SIZE / 2) as Quantity,
             // All other types are 32-bit.
             _ => 2,
         }
@@ -49,7 +49,8 @@ impl LabJackDataType {
             LabJackDataType::Uint32 => LabJackDataValue::Uint32(0),
             LabJackDataType::Uint64 => LabJackDataValue::Uint64(0),
             LabJackDataType::Int32 => LabJackDataValue::Int32(0),
-            LabJackDataType::String | LabJackDataType::Byte => LabJackDataValue::Byte(0),
+            LabJackDataType::String => LabJackDataValue::String(LabJackString::default()),
+            LabJackDataType::Byte => LabJackDataValue::Byte(0),
         }
     }
 }
@@ -58,6 +59,106 @@ pub struct DataValue<T: DataType> {
     pub value: T::Value,
 }
 
+/// The width of every LabJack string, in bytes, spanning 25 Modbus registers.
+pub const LABJACK_STRING_SIZE: usize = 50;
+
+/// A string held by a `STRING` register, such as `DEVICE_NAME_DEFAULT`.
+///
+/// LabJack strings are a fixed width of [`LABJACK_STRING_SIZE`] bytes, null-terminated
+/// where shorter, so hold at most 49 bytes of text.
+///
+/// ```
+/// use labjack::prelude::*;
+///
+/// let name = LabJackString::try_from("Bench T7").expect("Must fit");
+/// assert_eq!(name.as_str().expect("Must be UTF-8"), "Bench T7");
+/// assert_eq!(name.bytes().len(), LABJACK_STRING_SIZE);
+///
+/// assert!(LabJackString::try_from("x".repeat(LABJACK_STRING_SIZE).as_str()).is_err());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct LabJackString([u8; LABJACK_STRING_SIZE]);
+
+impl LabJackString {
+    /// Decodes a string from the [`LABJACK_STRING_SIZE`] bytes read from a register.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        bytes
+            .try_into()
+            .map(LabJackString)
+            .map_err(|_| Error::InvalidData(Reason::DecodingError))
+    }
+
+    /// The bytes of the string, up to the null terminator.
+    pub fn as_bytes(&self) -> &[u8] {
+        let end = self
+            .0
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(LABJACK_STRING_SIZE);
+
+        self.0.get(..end).unwrap_or_default()
+    }
+
+    /// The text of the string, provided it is valid UTF-8.
+    pub fn as_str(&self) -> Result<&str, Error> {
+        std::str::from_utf8(self.as_bytes()).map_err(|_| Error::InvalidData(Reason::DecodingError))
+    }
+
+    /// The full width of the string as written to a register, padded with nulls.
+    pub fn bytes(&self) -> [u8; LABJACK_STRING_SIZE] {
+        self.0
+    }
+}
+
+impl Default for LabJackString {
+    fn default() -> Self {
+        LabJackString([0; LABJACK_STRING_SIZE])
+    }
+}
+
+impl TryFrom<&str> for LabJackString {
+    type Error = Error;
+
+    /// Fails if the text does not leave room for the null terminator.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut bytes = [0; LABJACK_STRING_SIZE];
+        bytes
+            .get_mut(..value.len())
+            .filter(|_| value.len() < LABJACK_STRING_SIZE)
+            .ok_or(Error::InvalidData(Reason::EncodingError))?
+            .copy_from_slice(value.as_bytes());
+
+        Ok(LabJackString(bytes))
+    }
+}
+
+impl Display for LabJackString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(self.as_bytes()))
+    }
+}
+
+impl Debug for LabJackString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", String::from_utf8_lossy(self.as_bytes()))
+    }
+}
+
+impl Serialize for LabJackString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&String::from_utf8_lossy(self.as_bytes()))
+    }
+}
+
+impl<'de> Deserialize<'de> for LabJackString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        LabJackString::try_from(value.as_str()).map_err(|_| {
+            serde::de::Error::custom(format!("string exceeds {} bytes", LABJACK_STRING_SIZE - 1))
+        })
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum LabJackDataValue {
     Uint16(u16),
@@ -66,6 +167,14 @@ pub enum LabJackDataValue {
     Int32(i32),
     Float32(f32),
     Byte(u8),
+    String(LabJackString),
+}
+
+/// The bytes of a value of exactly `N` bytes.
+fn exact<const N: usize>(bytes: &[u8]) -> Result<[u8; N], Error> {
+    bytes
+        .try_into()
+        .map_err(|_| Error::InvalidData(Reason::DecodingError))
 }
 
 impl From<LabJackDataValue> for f64 {
@@ -77,10 +186,34 @@ impl From<LabJackDataValue> for f64 {
             LabJackDataValue::Int32(x) => x as f64,
             LabJackDataValue::Float32(x) => x as f64,
             LabJackDataValue::Byte(x) => x as f64,
+            // A string is no number, even where its text reads as one.
+            LabJackDataValue::String(_) => f64::NAN,
         }
     }
 }
 
+macro_rules! impl_from {
+    ($($value:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$value> for LabJackDataValue {
+                fn from(value: $value) -> Self {
+                    LabJackDataValue::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from! {
+    u16 => Uint16,
+    u32 => Uint32,
+    u64 => Uint64,
+    i32 => Int32,
+    f32 => Float32,
+    u8 => Byte,
+    LabJackString => String,
+}
+
 impl LabJackDataValue {
     pub fn r#type(&self) -> LabJackDataType {
         match self {
@@ -90,55 +223,99 @@ impl LabJackDataValue {
             LabJackDataValue::Int32(_) => LabJackDataType::Int32,
             LabJackDataValue::Float32(_) => LabJackDataType::Float32,
             LabJackDataValue::Byte(_) => LabJackDataType::Byte,
+            LabJackDataValue::String(_) => LabJackDataType::String,
         }
     }
 
-    /// Union-Backed Downcast to a HOT.
-    pub fn as_f64(&self) -> f64 {
-        f64::from(*self)
-    }
+    /// Converts the value to the given data type, such that it may be written to a register
+    /// of that type. The conversion must be exact, so a value out of the range of the type,
+    /// or which would lose precision, is rejected. Strings only convert to strings.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// let value = LabJackDataValue::from(5u16);
+    /// assert_eq!(value.coerce_to(LabJackDataType::Float32).ok(), Some(LabJackDataValue::Float32(5.0)));
+    ///
+    /// assert!(LabJackDataValue::from(1.5f32).coerce_to(LabJackDataType::Uint16).is_err());
+    /// assert!(LabJackDataValue::from(-1).coerce_to(LabJackDataType::Uint32).is_err());
+    /// ```
+    pub fn coerce_to(&self, data_type: LabJackDataType) -> Result<LabJackDataValue, Error> {
+        let cannot_coerce = || Error::InvalidData(Reason::CannotCoerce(*self, data_type));
 
-    pub(crate) fn decode_bytes<T: FromPrimitive>(bytes: &[u8]) -> Result<T, Error> {
-        let be_value = match bytes.len() {
-            2 => u16::from_be_bytes(
-                bytes
-                    .try_into()
-                    .map_err(|_| Error::InvalidData(Reason::DecodingError))?,
-            )
-            .to_f64(),
-            4 => u32::from_be_bytes(
-                bytes
-                    .try_into()
-                    .map_err(|_| Error::InvalidData(Reason::DecodingError))?,
-            )
-            .to_f64(),
-            _ => None,
+        if self.r#type() == data_type {
+            return Ok(*self);
+        }
+
+        // Every numeric value is held exactly by an i128, besides fractional floats.
+        let integer = match *self {
+            LabJackDataValue::Uint16(x) => i128::from(x),
+            LabJackDataValue::Uint32(x) => i128::from(x),
+            LabJackDataValue::Uint64(x) => i128::from(x),
+            LabJackDataValue::Int32(x) => i128::from(x),
+            LabJackDataValue::Byte(x) => i128::from(x),
+            LabJackDataValue::Float32(x) if x.is_finite() && x.fract() == 0.0 => x as i128,
+            LabJackDataValue::Float32(_) | LabJackDataValue::String(_) => {
+                return Err(cannot_coerce())
+            }
         };
 
-        be_value
-            .and_then(T::from_f64)
-            .ok_or(Error::InvalidData(Reason::DecodingError))
+        match data_type {
+            LabJackDataType::Uint16 => u16::try_from(integer).map(LabJackDataValue::Uint16).ok(),
+            LabJackDataType::Uint32 => u32::try_from(integer).map(LabJackDataValue::Uint32).ok(),
+            LabJackDataType::Uint64 => u64::try_from(integer).map(LabJackDataValue::Uint64).ok(),
+            LabJackDataType::Int32 => i32::try_from(integer).map(LabJackDataValue::Int32).ok(),
+            LabJackDataType::Byte => u8::try_from(integer).map(LabJackDataValue::Byte).ok(),
+            // Only integers of at most 24 significant bits survive the round trip.
+            LabJackDataType::Float32 => {
+                let float = integer as f32;
+                (float as i128 == integer).then_some(LabJackDataValue::Float32(float))
+            }
+            LabJackDataType::String => None,
+        }
+        .ok_or_else(cannot_coerce)
     }
 
+    /// Union-Backed Downcast to a HOT.
+    pub fn as_f64(&self) -> f64 {
+        f64::from(*self)
+    }
+
+    /// Decodes a value of the given type from the big-endian bytes read from its registers,
+    /// reinterpreting the bits exactly. This is the inverse of [`LabJackDataValue::bytes`].
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// let value = LabJackDataValue::from_bytes(LabJackDataType::Float32, &[0x40, 0x53, 0x33, 0x33]);
+    /// assert_eq!(value.expect("Must decode"), LabJackDataValue::Float32(3.3));
+    /// ```
     pub fn from_bytes(data_type: LabJackDataType, bytes: &[u8]) -> Result<Self, Error> {
         match data_type {
-            LabJackDataType::Uint16 => Ok(LabJackDataValue::Uint16(
-                LabJackDataValue::decode_bytes::<u16>(bytes)?, // u16::from_be_bytes(bytes.try_into().map_err(|_| Error::InvalidData(Reason::DecodingError))?)
-            )),
-            LabJackDataType::Uint32 => Ok(LabJackDataValue::Uint32(
-                LabJackDataValue::decode_bytes::<u32>(bytes)?, // u32::from_be_bytes(bytes.try_into().map_err(|_| Error::InvalidData(Reason::DecodingError))?)
-            )),
-            LabJackDataType::Int32 => Ok(LabJackDataValue::Int32(
-                LabJackDataValue::decode_bytes::<i32>(bytes)?, // i32::from_be_bytes(bytes.try_into().map_err(|_| Error::InvalidData(Reason::DecodingError))?)
-            )),
-            LabJackDataType::Float32 => Ok(LabJackDataValue::Float32(
-                LabJackDataValue::decode_bytes::<f32>(bytes)?, // f32::from_be_bytes(bytes.try_into().map_err(|_| Error::InvalidData(Reason::DecodingError))?)
-            )),
-            LabJackDataType::Uint64 => Ok(LabJackDataValue::Uint64(
-                LabJackDataValue::decode_bytes::<u64>(bytes)?, // f32::from_be_bytes(bytes.try_into().map_err(|_| Error::InvalidData(Reason::DecodingError))?)
-            )),
-            LabJackDataType::Byte => unimplemented!(),
-            LabJackDataType::String => unimplemented!(),
+            LabJackDataType::Uint16 => {
+                Ok(LabJackDataValue::Uint16(u16::from_be_bytes(exact(bytes)?)))
+            }
+            LabJackDataType::Uint32 => {
+                Ok(LabJackDataValue::Uint32(u32::from_be_bytes(exact(bytes)?)))
+            }
+            LabJackDataType::Uint64 => {
+                Ok(LabJackDataValue::Uint64(u64::from_be_bytes(exact(bytes)?)))
+            }
+            LabJackDataType::Int32 => {
+                Ok(LabJackDataValue::Int32(i32::from_be_bytes(exact(bytes)?)))
+            }
+            LabJackDataType::Float32 => {
+                Ok(LabJackDataValue::Float32(f32::from_be_bytes(exact(bytes)?)))
+            }
+            // A byte is carried in the low half of its Modbus register, as in a byte
+            // buffer, so whatever the device leaves in the high half is ignored.
+            LabJackDataType::Byte => {
+                let [_, low] = exact(bytes)?;
+                Ok(LabJackDataValue::Byte(low))
+            }
+            LabJackDataType::String => {
+                Ok(LabJackDataValue::String(LabJackString::from_bytes(bytes)?))
+            }
         }
     }
 
@@ -149,7 +326,145 @@ impl LabJackDataValue {
             LabJackDataValue::Uint64(x) => x.to_be_bytes().to_vec(),
             LabJackDataValue::Int32(x) => x.to_be_bytes().to_vec(),
             LabJackDataValue::Float32(x) => x.to_be_bytes().to_vec(),
-            LabJackDataValue::Byte(x) => x.to_be_bytes().to_vec(),
+            LabJackDataValue::Byte(x) => u16::from(*x).to_be_bytes().to_vec(),
+            LabJackDataValue::String(x) => x.bytes().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    /// Encodes the value, and checks decoding yields the very same bits.
+    fn round_trip(value: LabJackDataValue) {
+        let bytes = value.bytes();
+        assert_eq!(bytes.len(), 2 * value.r#type().size() as usize);
+
+        let decoded = LabJackDataValue::from_bytes(value.r#type(), &bytes).expect("Must decode");
+        assert_eq!(decoded.bytes(), bytes, "value={value:?}");
+    }
+
+    #[test]
+    fn round_trips_each_type() {
+        for value in [0, 1, 0x1234, u16::MAX] {
+            round_trip(LabJackDataValue::Uint16(value));
+        }
+        for value in [0, 1, 0x00112233, u32::MAX] {
+            round_trip(LabJackDataValue::Uint32(value));
+        }
+        for value in [0, 1, 0x0011223344556677, u64::MAX] {
+            round_trip(LabJackDataValue::Uint64(value));
+        }
+        for value in [0, 1, -1, i32::MIN, i32::MAX] {
+            round_trip(LabJackDataValue::Int32(value));
+        }
+        for value in [
+            0.0,
+            -0.0,
+            3.3,
+            -273.15,
+            f32::MIN_POSITIVE,
+            f32::MAX,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NAN,
+        ] {
+            round_trip(LabJackDataValue::Float32(value));
+        }
+        for value in [0, 1, u8::MAX] {
+            round_trip(LabJackDataValue::Byte(value));
+        }
+
+        let name = LabJackString::try_from("T7-Pro").expect("Must fit");
+        round_trip(LabJackDataValue::String(name));
+    }
+
+    #[test]
+    fn decodes_bits_exactly() {
+        let decode = |data_type, bytes: &[u8]| {
+            LabJackDataValue::from_bytes(data_type, bytes).expect("Must decode")
+        };
+
+        assert_eq!(
+            decode(LabJackDataType::Float32, &[0x40, 0x53, 0x33, 0x33]),
+            LabJackDataValue::Float32(3.3)
+        );
+        assert_eq!(
+            decode(LabJackDataType::Int32, &[0xFF, 0xFF, 0xFF, 0xFE]),
+            LabJackDataValue::Int32(-2)
+        );
+        assert_eq!(
+            decode(LabJackDataType::Uint64, &[0x80, 0, 0, 0, 0, 0, 0, 0x01]),
+            LabJackDataValue::Uint64(0x8000_0000_0000_0001)
+        );
+        assert_eq!(
+            decode(LabJackDataType::Byte, &[0x00, 0x7F]),
+            LabJackDataValue::Byte(0x7F)
+        );
+        assert_eq!(
+            decode(LabJackDataType::Byte, &[0x01, 0x7F]),
+            LabJackDataValue::Byte(0x7F)
+        );
+    }
+
+    #[test]
+    fn coerces_exactly() {
+        let coerce = |value: LabJackDataValue, data_type| value.coerce_to(data_type).ok();
+
+        assert_eq!(
+            coerce(65535u32.into(), LabJackDataType::Uint16),
+            Some(LabJackDataValue::Uint16(u16::MAX))
+        );
+        assert_eq!(coerce(65536u32.into(), LabJackDataType::Uint16), None);
+        assert_eq!(coerce((-1).into(), LabJackDataType::Uint64), None);
+        assert_eq!(
+            coerce(u64::from(u32::MAX).into(), LabJackDataType::Uint32),
+            Some(LabJackDataValue::Uint32(u32::MAX))
+        );
+
+        // Float32 holds integers exactly only up to 24 bits.
+        assert_eq!(
+            coerce((1u32 << 24).into(), LabJackDataType::Float32),
+            Some(LabJackDataValue::Float32(16777216.0))
+        );
+        assert_eq!(
+            coerce(((1u32 << 24) + 1).into(), LabJackDataType::Float32),
+            None
+        );
+
+        assert_eq!(
+            coerce((-3.0f32).into(), LabJackDataType::Int32),
+            Some(LabJackDataValue::Int32(-3))
+        );
+        assert_eq!(coerce(3.3f32.into(), LabJackDataType::Int32), None);
+        assert_eq!(coerce(f32::NAN.into(), LabJackDataType::Uint32), None);
+        assert_eq!(coerce(f32::INFINITY.into(), LabJackDataType::Uint64), None);
+
+        let name = LabJackString::try_from("7").expect("Must fit");
+        assert_eq!(coerce(name.into(), LabJackDataType::Uint16), None);
+        assert_eq!(coerce(7u16.into(), LabJackDataType::String), None);
+    }
+
+    #[test]
+    fn rejects_wrong_widths() {
+        let cases: [(LabJackDataType, &[u8]); 6] = [
+            (LabJackDataType::Uint16, &[0x01]),
+            (LabJackDataType::Uint32, &[0x01, 0x02]),
+            (LabJackDataType::Float32, &[0x01, 0x02, 0x03, 0x04, 0x05]),
+            (LabJackDataType::Uint64, &[0x01, 0x02, 0x03, 0x04]),
+            (LabJackDataType::Byte, &[0x01]),
+            (LabJackDataType::String, &[0x41; 10]),
+        ];
+
+        for (data_type, bytes) in cases {
+            assert!(
+                matches!(
+                    LabJackDataValue::from_bytes(data_type, bytes),
+                    Err(Error::InvalidData(Reason::DecodingError))
+                ),
+                "type={data_type:?}"
+            );
         }
     }
 }
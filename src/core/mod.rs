@@ -7,7 +7,11 @@ pub mod dist;
 pub mod ef;
 pub mod func;
 pub mod modbus;
+pub(crate) mod ranged_name;
+pub mod reading;
+pub mod register_map;
 pub mod sets;
+pub mod values;
 
 pub use client::*;
 pub use connection::*;
@@ -17,4 +21,7 @@ pub use device::*;
 pub use dist::*;
 pub use func::*;
 pub use modbus::*;
+pub use reading::*;
+pub use register_map::*;
 pub use sets::*;
+pub use values::*;
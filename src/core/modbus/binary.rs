@@ -18,7 +18,7 @@ pub fn unpack_bits(bytes: &[u8], count: u16) -> Vec<Coil> {
 
 pub fn pack_bits(bits: &[Coil]) -> Vec<u8> {
     let bitcount = bits.len();
-    let packed_size = bitcount / 8 + if bitcount % 8 > 0 { 1 } else { 0 };
+    let packed_size = bitcount / 8 + if bitcount.is_multiple_of(8) { 0 } else { 1 };
     let mut res = vec![0; packed_size];
 
     for (i, b) in bits.iter().enumerate() {
@@ -47,7 +47,7 @@ pub fn unpack_bytes(data: &[u16]) -> Vec<u8> {
 pub fn pack_bytes(bytes: &[u8]) -> Result<Vec<u16>, io::Error> {
     let size = bytes.len();
     // check if we can create u16s from bytes by packing two u8s together without rest
-    if size % 2 != 0 {
+    if !size.is_multiple_of(2) {
         return Err(io::Error::from_raw_os_error(22));
     }
 
@@ -72,7 +72,7 @@ fn test_unpack_bits() {
 
 #[test]
 fn test_pack_bits() {
-    assert_eq!(pack_bits(&[]), &[]);
+    assert!(pack_bits(&[]).is_empty());
     assert_eq!(pack_bits(&[Coil::On]), &[1]);
     assert_eq!(pack_bits(&[Coil::Off]), &[0]);
     assert_eq!(pack_bits(&[Coil::On, Coil::Off]), &[1]);
@@ -86,7 +86,7 @@ fn test_pack_bits() {
 
 #[test]
 fn test_unpack_bytes() {
-    assert_eq!(unpack_bytes(&[]), &[]);
+    assert!(unpack_bytes(&[]).is_empty());
     assert_eq!(unpack_bytes(&[0]), &[0, 0]);
     assert_eq!(unpack_bytes(&[1]), &[0, 1]);
     assert_eq!(unpack_bytes(&[0xffff]), &[0xff, 0xff]);
@@ -96,7 +96,7 @@ fn test_unpack_bytes() {
 
 #[test]
 fn test_pack_bytes() {
-    assert_eq!(pack_bytes(&[]).unwrap(), &[]);
+    assert!(pack_bytes(&[]).unwrap().is_empty());
     assert_eq!(pack_bytes(&[0, 0]).unwrap(), &[0]);
     assert_eq!(pack_bytes(&[0, 1]).unwrap(), &[1]);
     assert_eq!(pack_bytes(&[1, 0]).unwrap(), &[256]);
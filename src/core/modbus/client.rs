@@ -4,6 +4,11 @@ pub const MODBUS_UNIT_ID: u8 = 1;
 pub const MODBUS_HEADER_SIZE: usize = 7;
 pub const MODBUS_MAX_PACKET_SIZE: usize = 260;
 
+/// The largest number of registers which may be read in one Read Holding Registers (0x03) request.
+pub const MODBUS_MAX_READ_REGISTERS: Quantity = 125;
+/// The largest number of registers which may be written in one Write Multiple Registers (0x10) request.
+pub const MODBUS_MAX_WRITE_REGISTERS: Quantity = 123;
+
 pub trait Client: Transport {
     fn read_register(
         &mut self,
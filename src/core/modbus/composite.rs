@@ -77,6 +77,7 @@ impl<'a> Compositor<'a> {
     }
 
     pub fn compose_write(&mut self, function: &WriteFunction) -> Result<ComposedMessage, Error> {
+        let value = function.value()?;
         let size = function.0.data_type.size();
         let bytes = size * 2;
 
@@ -87,7 +88,7 @@ impl<'a> Compositor<'a> {
         content.write_u16::<BigEndian>(function.0.address)?;
         content.write_u16::<BigEndian>(size)?;
 
-        let bytes = function.1.bytes();
+        let bytes = value.bytes();
         content.write_u8(bytes.len() as u8)?;
 
         for v in bytes {
@@ -102,10 +103,54 @@ impl<'a> Compositor<'a> {
         })
     }
 
-    pub fn compose_feedback(&mut self, fns: &[FeedbackFunction]) -> Result<ComposedMessage, Error> {
-        const BASE_FRAME_SIZE: usize = 4;
+    pub fn compose_read_block(
+        &mut self,
+        function: &ReadBlockFunction,
+    ) -> Result<ComposedMessage, Error> {
+        let word_size = function.quantity()?;
+
+        let header = Header::new(self, 6u16);
+        let mut content = header.pack()?;
+
+        content.write_u8(function.code())?;
+        content.write_u16::<BigEndian>(function.address)?;
+        content.write_u16::<BigEndian>(word_size)?;
+
+        Ok(ComposedMessage {
+            content,
+            header,
+            expected_bytes: 2 * word_size as usize,
+        })
+    }
+
+    pub fn compose_write_block(
+        &mut self,
+        function: &WriteBlockFunction,
+    ) -> Result<ComposedMessage, Error> {
+        let size = function.quantity()?;
+        let bytes = size * 2;
+
+        let header = Header::new(self, bytes + MODBUS_HEADER_SIZE as u16);
+        let mut content = header.pack()?;
+
+        content.write_u8(function.code())?;
+        content.write_u16::<BigEndian>(function.address)?;
+        content.write_u16::<BigEndian>(size)?;
+        content.write_u8(bytes as u8)?;
+
+        for value in &function.values {
+            content.write_all(&value.bytes())?;
+        }
+
+        Ok(ComposedMessage {
+            content,
+            header,
+            // Device will relay starting address and num. registers.
+            expected_bytes: 4usize,
+        })
+    }
 
-        let mut read_return_size = 0;
+    pub fn compose_feedback(&mut self, fns: &[FeedbackFunction]) -> Result<ComposedMessage, Error> {
         // Each frame is broken into a read or write frame, specified here
         // as the two variants of the feedback functions. There is a common
         // schema for each for the first 4 bytes. After which only write-functions
@@ -115,20 +160,13 @@ impl<'a> Compositor<'a> {
         //
         // Example from LabJack is given here:
         // https://support.labjack.com/docs/protocol-details-direct-modbus-tcp#ProtocolDetails[DirectModbusTCP]-ModbusFeedback(MBFB,function#76)
-        let composed_size = fns.iter().fold(2, |acc, f| match f {
-            FeedbackFunction::ReadRegister(reg) => {
-                read_return_size += reg.data_type.size();
-                acc + BASE_FRAME_SIZE
-            }
-            FeedbackFunction::WriteRegister(reg, ..) => {
-                acc + BASE_FRAME_SIZE + reg.data_type.size() as usize
-            }
-        });
+        let composed_size = fns.iter().fold(2, |acc, f| acc + f.request_size());
+        let read_return_size = fns.iter().map(FeedbackFunction::response_size).sum();
 
         let header = Header::new(self, composed_size as u16);
         let mut content = header.pack()?;
 
-        content.write_u8(0x4C)?; // 0x4C is Feedback Code (76)
+        content.write_u8(MODBUS_FEEDBACK_CODE)?;
 
         for frame in fns {
             content.write_u8(frame.code())?;
@@ -140,7 +178,7 @@ impl<'a> Compositor<'a> {
 
             // Write data for write-function
             if let FeedbackFunction::WriteRegister(.., value) = frame {
-                let bytes = value.bytes();
+                let bytes = value.coerce_to(register.data_type)?.bytes();
                 content.write_all(&bytes)?;
             }
         }
@@ -148,11 +186,93 @@ impl<'a> Compositor<'a> {
         Ok(ComposedMessage {
             content,
             header,
-            expected_bytes: 7 + read_return_size as usize,
+            expected_bytes: read_return_size,
         })
     }
 }
 
+/// Splits a set of feedback frames into consecutive batches, such that neither the
+/// request nor the response of any one batch exceeds the [`MAX_DATA_LENGTH`] of a packet.
+///
+/// Frames are never reordered, so the results of each batch may be concatenated
+/// to produce the result of the whole set. A single frame which cannot fit into
+/// a packet on its own is rejected.
+pub fn partition_feedback(fns: &[FeedbackFunction]) -> Result<Vec<&[FeedbackFunction]>, Error> {
+    // Header, followed by the feedback function code.
+    const BASE_SIZE: usize = MODBUS_HEADER_SIZE + 1;
+
+    let mut batches = vec![];
+    let mut start = 0;
+    let (mut request_size, mut response_size) = (BASE_SIZE, BASE_SIZE);
+
+    for (index, frame) in fns.iter().enumerate() {
+        if BASE_SIZE + frame.request_size() > MAX_DATA_LENGTH
+            || BASE_SIZE + frame.response_size() > MAX_DATA_LENGTH
+        {
+            return Err(Error::InvalidData(Reason::SendBufferTooBig));
+        }
+
+        if request_size + frame.request_size() > MAX_DATA_LENGTH
+            || response_size + frame.response_size() > MAX_DATA_LENGTH
+        {
+            batches.push(fns.get(start..index).ok_or(Error::InvalidFunction)?);
+            start = index;
+            (request_size, response_size) = (BASE_SIZE, BASE_SIZE);
+        }
+
+        request_size += frame.request_size();
+        response_size += frame.response_size();
+    }
+
+    if start < fns.len() {
+        batches.push(fns.get(start..).ok_or(Error::InvalidFunction)?);
+    }
+
+    Ok(batches)
+}
+
+/// Decodes the data section of a feedback response, yielding one value per read-frame
+/// in the order the frames were given. Write-frames have no response data, and so are
+/// skipped.
+pub fn decode_feedback(
+    fns: &[FeedbackFunction],
+    data: &[u8],
+) -> Result<Vec<LabJackDataValue>, Error> {
+    decode_sequence(
+        fns.iter().filter_map(|frame| match frame {
+            FeedbackFunction::ReadRegister(register) => Some(register.data_type),
+            FeedbackFunction::WriteRegister(..) => None,
+        }),
+        data,
+    )
+}
+
+/// Decodes a run of back-to-back values, such as the response to a [`ReadBlockFunction`].
+/// The data must be entirely consumed by the given types.
+pub fn decode_sequence(
+    data_types: impl IntoIterator<Item = LabJackDataType>,
+    data: &[u8],
+) -> Result<Vec<LabJackDataValue>, Error> {
+    let mut offset = 0;
+    let mut values = vec![];
+
+    for data_type in data_types {
+        let size = 2 * data_type.size() as usize;
+        let bytes = data
+            .get(offset..offset + size)
+            .ok_or(Error::InvalidData(Reason::UnexpectedReplySize))?;
+
+        values.push(StandardDecoder { bytes }.decode_as(data_type)?);
+        offset += size;
+    }
+
+    if offset != data.len() {
+        return Err(Error::InvalidData(Reason::UnexpectedReplySize));
+    }
+
+    Ok(values)
+}
+
 impl Header {
     fn new(compositor: &mut Compositor, len: u16) -> Header {
         Header {
@@ -365,7 +485,7 @@ mod test {
             .expect("Must-compose");
 
         assert_eq!(transaction_id.to_be_bytes(), content[0..2]);
-        assert_eq!([0x00, 0x00, 0x00, 0x0C, 0x01, 0x4C], content[2..8]);
+        assert_eq!([0x00, 0x00, 0x00, 0x0E, 0x01, 0x4C], content[2..8]);
 
         // AIN55 Frame (Read-Frame)
         assert_eq!([0x00, 0x00, 0x6E, 0x02], content[8..12]);
@@ -376,4 +496,223 @@ mod test {
         // Writen AIN56 Value (15.0)
         assert_eq!(value_written.to_be_bytes(), content[16..])
     }
+
+    #[test]
+    fn partition_feedback_splits_large_sets() {
+        // Each AIN read returns 4 bytes, so the response is the limiting factor.
+        let functions = (0..300)
+            .map(|_| FeedbackFunction::ReadRegister(*AIN55))
+            .collect::<Vec<_>>();
+
+        let batches = partition_feedback(&functions).expect("Must partition");
+        assert_eq!(batches.iter().map(|b| b.len()).sum::<usize>(), 300);
+
+        for batch in &batches {
+            let response_size = batch.iter().map(|f| f.response_size()).sum::<usize>();
+            assert!(MODBUS_HEADER_SIZE + 1 + response_size <= MAX_DATA_LENGTH);
+        }
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), 258);
+    }
+
+    #[test]
+    fn partition_feedback_single_batch() {
+        let functions = &[
+            FeedbackFunction::ReadRegister(*AIN55),
+            FeedbackFunction::WriteRegister(*AIN56, LabJackDataValue::Float32(1.0)),
+        ];
+
+        let batches = partition_feedback(functions).expect("Must partition");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 2);
+    }
+
+    #[test]
+    fn decode_feedback_values() {
+        let functions = &[
+            FeedbackFunction::ReadRegister(*TEST_UINT16),
+            FeedbackFunction::WriteRegister(*AIN56, LabJackDataValue::Float32(1.0)),
+            FeedbackFunction::ReadRegister(*TEST_UINT32),
+        ];
+
+        let values =
+            decode_feedback(functions, &[0x00, 0x11, 0x00, 0x11, 0x22, 0x33]).expect("Must decode");
+
+        assert_eq!(
+            values,
+            vec![
+                LabJackDataValue::Uint16(0x0011),
+                LabJackDataValue::Uint32(0x00112233)
+            ]
+        );
+
+        assert!(decode_feedback(functions, &[0x00, 0x11, 0x00]).is_err());
+    }
+
+    #[test]
+    fn read_block_ain() {
+        let mut transaction_id = 0;
+        let mut compositor = Compositor::new(&mut transaction_id, MODBUS_UNIT_ID);
+
+        let function = ReadBlockFunction::span(*AIN0, 14).expect("Must be contiguous");
+        let ComposedMessage {
+            content,
+            expected_bytes,
+            ..
+        } = compositor
+            .compose_read_block(&function)
+            .expect("Must-compose");
+
+        assert_eq!(expected_bytes, 14 * 4);
+        assert_eq!(
+            [0x00, 0x00, 0x00, 0x06, 0x01, 0x03, 0x00, 0x00, 0x00, 0x1C],
+            content[2..]
+        );
+    }
+
+    #[test]
+    fn read_block_rejects_gaps() {
+        assert!(ReadBlockFunction::new(&[*AIN0, *AIN1, *AIN2]).is_ok());
+        assert!(matches!(
+            ReadBlockFunction::new(&[*AIN0, *AIN2]),
+            Err(Error::InvalidData(Reason::NonContiguousRegisters))
+        ));
+        assert!(ReadBlockFunction::new(&[]).is_err());
+    }
+
+    #[test]
+    fn read_block_rejects_oversized() {
+        // 63 Float32 values span 126 registers, one beyond the limit.
+        assert!(ReadBlockFunction::span(*AIN0, 62).is_ok());
+        assert!(matches!(
+            ReadBlockFunction::span(*AIN0, 63),
+            Err(Error::InvalidData(Reason::SendBufferTooBig))
+        ));
+    }
+
+    #[test]
+    fn block_rejects_address_overflow() {
+        let last = Register {
+            address: u16::MAX - 1,
+            ..*AIN0
+        };
+        let next = Register {
+            address: u16::MAX,
+            ..*AIN0
+        };
+
+        assert!(matches!(
+            ReadBlockFunction::new(&[last, next]),
+            Err(Error::InvalidData(Reason::NonContiguousRegisters))
+        ));
+
+        let oversized = ReadBlockFunction {
+            address: 0,
+            data_types: vec![LabJackDataType::Uint64; 0x4000],
+        };
+        assert!(matches!(
+            oversized.quantity(),
+            Err(Error::InvalidData(Reason::SendBufferTooBig))
+        ));
+    }
+
+    #[test]
+    fn write_block_dac() {
+        let mut transaction_id = 0;
+        let mut compositor = Compositor::new(&mut transaction_id, MODBUS_UNIT_ID);
+
+        let function = WriteBlockFunction::new(
+            &[*DAC0, *DAC1],
+            &[
+                LabJackDataValue::Float32(3.3f32),
+                LabJackDataValue::Float32(3.3f32),
+            ],
+        )
+        .expect("Must be contiguous");
+
+        let ComposedMessage { content, .. } = compositor
+            .compose_write_block(&function)
+            .expect("Must-compose");
+
+        assert_eq!(
+            [
+                0x00, 0x00, 0x00, 0x0F, 0x01, 0x10, 0x03, 0xE8, 0x00, 0x04, 0x08, 0x40, 0x53, 0x33,
+                0x33, 0x40, 0x53, 0x33, 0x33
+            ],
+            content[2..]
+        );
+    }
+
+    #[test]
+    fn write_block_coerces_types() {
+        let function =
+            WriteBlockFunction::new(&[*DAC0], &[LabJackDataValue::Uint16(1)]).expect("Must coerce");
+        assert_eq!(function.values, vec![LabJackDataValue::Float32(1.0)]);
+
+        assert!(matches!(
+            WriteBlockFunction::span(*TEST_UINT16, &[LabJackDataValue::Float32(1.5)]),
+            Err(Error::InvalidData(Reason::CannotCoerce(..)))
+        ));
+        assert!(matches!(
+            WriteBlockFunction::new(&[*DAC0, *DAC1], &[LabJackDataValue::Float32(1.0)]),
+            Err(Error::InvalidData(Reason::RegisterMismatch))
+        ));
+    }
+
+    #[test]
+    fn buffers_split_across_packets() {
+        let reads = ReadBlockFunction::buffer(*SPI_DATA_RX, 301).expect("Must be a buffer");
+        let words = reads
+            .iter()
+            .map(|f| f.quantity().expect("Must fit"))
+            .collect::<Vec<_>>();
+        assert_eq!(words, vec![125, 26]);
+        assert!(reads.iter().all(|f| f.address == SPI_DATA_RX.address));
+
+        // An odd number of bytes is padded to fill the final register.
+        let writes = WriteBlockFunction::buffer(*SPI_DATA_TX, &[0xAA; 247]).expect("Must split");
+        let words = writes
+            .iter()
+            .map(|f| f.quantity().expect("Must fit"))
+            .collect::<Vec<_>>();
+        assert_eq!(words, vec![123, 1]);
+        assert_eq!(writes[1].values, vec![LabJackDataValue::Uint16(0xAA00)]);
+
+        assert!(matches!(
+            ReadBlockFunction::buffer(*AIN0, 2),
+            Err(Error::InvalidData(Reason::NotABuffer))
+        ));
+
+        // Buffers of words are split on whole values.
+        let reads =
+            ReadBlockFunction::buffer_values(*USER_RAM_FIFO0_DATA_U32, 100).expect("Must split");
+        let words = reads
+            .iter()
+            .map(|f| f.quantity().expect("Must fit"))
+            .collect::<Vec<_>>();
+        assert_eq!(words, vec![124, 76]);
+        assert!(matches!(
+            ReadBlockFunction::buffer(*USER_RAM_FIFO0_DATA_U32, 4),
+            Err(Error::InvalidData(Reason::NotAByteBuffer))
+        ));
+    }
+
+    #[test]
+    fn compose_write_coerces_value() {
+        let mut transaction_id = 0;
+        let mut compositor = Compositor::new(&mut transaction_id, MODBUS_UNIT_ID);
+
+        // Serialises as the register's Float32, rather than the value's Uint16.
+        let function = WriteFunction(*DAC0, LabJackDataValue::Uint16(5));
+        let ComposedMessage { content, .. } =
+            compositor.compose_write(&function).expect("Must compose");
+        assert_eq!(content[13..], 5.0f32.to_be_bytes());
+
+        let function = WriteFunction(*TEST_UINT16, LabJackDataValue::Uint32(70000));
+        assert!(matches!(
+            compositor.compose_write(&function),
+            Err(Error::InvalidData(Reason::CannotCoerce(..)))
+        ));
+    }
 }
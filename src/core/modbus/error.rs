@@ -1,7 +1,12 @@
+use crate::prelude::{
+    AccessControl, DeviceType, LabJackDataType, LabJackDataValue, LabJackErrorCode,
+};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 use std::io;
 
 enum_from_primitive! {
-    #[derive(Debug, PartialEq)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
     /// Modbus exception codes returned from the server.
     pub enum ExceptionCode {
         IllegalFunction         = 0x01,
@@ -18,6 +23,29 @@ enum_from_primitive! {
     }
 }
 
+impl ExceptionCode {
+    /// The equivalent [`LabJackErrorCode`], which LabJack offsets from 1200.
+    pub fn error_code(&self) -> Option<LabJackErrorCode> {
+        LabJackErrorCode::from_code(1200 + *self as u32)
+    }
+}
+
+impl LabJackErrorCode {
+    /// The numeric value of the code.
+    pub const fn code(&self) -> u32 {
+        *self as u32
+    }
+}
+
+impl Display for LabJackErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.description() {
+            Some(description) => write!(f, "{} ({}): {description}", self.name(), self.code()),
+            None => write!(f, "{} ({})", self.name(), self.code()),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Reason {
     UnexpectedReplySize,
@@ -29,7 +57,18 @@ pub enum Reason {
     EncodingError,
     InvalidByteorder,
     RegisterMismatch,
+    NonContiguousRegisters,
     NoDataAtRegister,
+    /// No register is known by the given name.
+    UnknownRegister(String),
+    /// The value cannot be represented exactly as the data type of the register it is
+    /// written to, see [`LabJackDataValue::coerce_to`].
+    CannotCoerce(LabJackDataValue, LabJackDataType),
+    /// The register is not a buffer.
+    NotABuffer,
+    /// The register is a buffer of values other than bytes, and so is read and written
+    /// as values, see [`ReadBlockFunction::buffer_values`](crate::prelude::ReadBlockFunction::buffer_values).
+    NotAByteBuffer,
     Custom(String),
 }
 
@@ -39,16 +78,71 @@ impl From<Reason> for Error {
     }
 }
 
+/// Why a register may not be used with the connected device. Each names the register,
+/// which may be one loaded at runtime into a [`RegisterMap`](crate::prelude::RegisterMap).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incompatibility {
+    /// The device does not have the register.
+    Device {
+        register: Cow<'static, str>,
+        device: DeviceType,
+    },
+    /// The register was introduced by a later firmware version than that installed.
+    Firmware {
+        register: Cow<'static, str>,
+        device: DeviceType,
+        required: f32,
+        installed: f32,
+    },
+    /// The register may only be read, or only be written, as given by its `access`.
+    Access {
+        register: Cow<'static, str>,
+        access: AccessControl,
+    },
+}
+
+impl Display for Incompatibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Incompatibility::Device { register, device } => {
+                write!(f, "{register} is not available on the {device}")
+            }
+            Incompatibility::Firmware {
+                register,
+                device,
+                required,
+                installed,
+            } => write!(
+                f,
+                "{register} requires firmware {required} or later, but the {device} runs {installed}"
+            ),
+            Incompatibility::Access { register, access } => match access {
+                AccessControl::ReadableCtrl => write!(f, "{register} may only be read"),
+                AccessControl::WritableCtrl => write!(f, "{register} may only be written"),
+                AccessControl::AllCtrl => write!(f, "{register} may be read and written"),
+            },
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum QueueError {
     QueueEmptyWhenRead,
     FrameSizeTooLarge,
+    /// A request with the same transaction id is still awaiting its response.
+    TransactionInUse,
+    /// The response slot was closed before a response was delivered.
+    SlotClosed,
 }
 
 #[derive(Debug)]
 pub enum Error {
     Queue(QueueError),
     Exception(ExceptionCode),
+    /// The device rejected a request, detailing why with the given code.
+    Device(LabJackErrorCode),
+    /// The register may not be used with the connected device, so no request was sent.
+    Incompatible(Incompatibility),
     Io(io::Error),
     InvalidResponse,
     InvalidData(Reason),
@@ -56,8 +150,38 @@ pub enum Error {
     ParseCoilError,
     ParseInfoError,
     DeviceNotFound,
+    /// The device did not accept a connection, or respond to a request, within the configured timeout.
+    Timeout,
+    /// The connection to the device was lost before the request completed.
+    Disconnected,
+    /// The transport was closed, and can no longer be used.
+    Closed,
 }
 
+impl Error {
+    /// The [`LabJackErrorCode`] describing the error, if it was reported by the device.
+    pub fn code(&self) -> Option<LabJackErrorCode> {
+        match self {
+            Error::Device(code) => Some(*code),
+            Error::Exception(exception) => exception.error_code(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self, self.code()) {
+            (Error::Io(err), _) => write!(f, "{err}"),
+            (Error::Incompatible(incompatibility), _) => write!(f, "{incompatibility}"),
+            (_, Some(code)) => write!(f, "{code}"),
+            (err, None) => write!(f, "{err:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl From<ExceptionCode> for Error {
     fn from(err: ExceptionCode) -> Error {
         Error::Exception(err)
@@ -69,3 +193,37 @@ impl From<io::Error> for Error {
         Error::Io(err)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn codes_from_table() {
+        let code = LabJackErrorCode::from_code(2370).expect("Must be known");
+        assert_eq!(code, LabJackErrorCode::AinRangeInvalid);
+        assert_eq!(code.name(), "AIN_RANGE_INVALID");
+        assert_eq!(code.code(), 2370);
+        assert_eq!(
+            code.to_string(),
+            "AIN_RANGE_INVALID (2370): Specified range not available on this device."
+        );
+
+        assert_eq!(LabJackErrorCode::LjSuccess.description(), None);
+        assert_eq!(LabJackErrorCode::from_code(1), None);
+    }
+
+    #[test]
+    fn exceptions_map_to_codes() {
+        assert_eq!(
+            ExceptionCode::IllegalDataAddress.error_code(),
+            Some(LabJackErrorCode::LjmeMbe2IllegalDataAddress)
+        );
+        assert_eq!(ExceptionCode::NegativeAcknowledge.error_code(), None);
+
+        let error = Error::Exception(ExceptionCode::IllegalDataAddress);
+        assert!(error
+            .to_string()
+            .starts_with("LJME_MBE2_ILLEGAL_DATA_ADDRESS (1202)"));
+    }
+}
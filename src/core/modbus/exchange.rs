@@ -0,0 +1,305 @@
+//! The request and response handling shared by the networked transports.
+//!
+//! Each transport only differs in how a composed message reaches the device, and how the
+//! response finds its way back. Those are provided through [`Exchange`], from which the
+//! remainder of a [`Transport`] is derived by the functions of this module.
+
+use crate::prelude::*;
+use crate::queue::limiter::Limiter;
+use crate::queue::router::{Router, Slot};
+use enum_primitive::FromPrimitive;
+use log::debug;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
+use tokio::sync::SemaphorePermit;
+
+/// A transport which sends composed Modbus messages and awaits their responses.
+pub(crate) trait Exchange: Sync {
+    /// Composes a message with the next transaction id.
+    fn compose<F>(&self, compose: F) -> Result<ComposedMessage, Error>
+    where
+        F: FnOnce(&mut Compositor) -> Result<ComposedMessage, Error>;
+
+    /// Sends a composed message and awaits the response with the same transaction id.
+    fn exchange(
+        &self,
+        content: &[u8],
+        header: &Header,
+    ) -> impl std::future::Future<Output = Result<(Header, Vec<u8>), Error>> + Send;
+}
+
+/// The state shared by every clone of a networked transport, through which its requests
+/// are numbered, bounded and matched to their responses.
+#[derive(Debug, Clone)]
+pub(crate) struct Pipeline {
+    transaction_id: Arc<AtomicU16>,
+    unit_id: u8,
+
+    router: Arc<Router>,
+    in_flight: Arc<Limiter>,
+}
+
+impl Pipeline {
+    pub(crate) fn new(unit_id: u8) -> Pipeline {
+        Pipeline {
+            transaction_id: Arc::new(AtomicU16::new(STARTING_TRANSACTION_ID)),
+            unit_id,
+
+            router: Router::new(),
+            in_flight: Arc::new(Limiter::new(DEFAULT_MAX_IN_FLIGHT)),
+        }
+    }
+
+    /// The router onto which the transport's listener publishes each response.
+    pub(crate) fn router(&self) -> Arc<Router> {
+        Arc::clone(&self.router)
+    }
+
+    /// Composes a message with the next transaction id. The id is shared by every
+    /// clone of the transport, so concurrent requests are never given the same id.
+    pub(crate) fn compose<F>(&self, compose: F) -> Result<ComposedMessage, Error>
+    where
+        F: FnOnce(&mut Compositor) -> Result<ComposedMessage, Error>,
+    {
+        let mut transaction_id = self.transaction_id.fetch_add(1, Ordering::Relaxed);
+        compose(&mut Compositor::new(&mut transaction_id, self.unit_id))
+    }
+
+    /// Waits for the request with the given transaction id to be permitted in flight,
+    /// then reserves the slot for its response. Both are held until the request ends.
+    ///
+    /// Time spent waiting for a permit does not count toward the request's timeout.
+    pub(crate) async fn reserve(
+        &self,
+        transaction_id: u16,
+    ) -> Result<(SemaphorePermit<'_>, Slot), Error> {
+        let permit = self.in_flight.acquire().await?;
+        let slot = self.router.register(transaction_id)?;
+
+        Ok((permit, slot))
+    }
+
+    /// Sets the number of requests which may be awaiting a response at once.
+    pub(crate) async fn set_max_in_flight(&self, max_in_flight: usize) {
+        self.in_flight.resize(max_in_flight).await;
+    }
+
+    /// The number of requests awaiting a response.
+    pub(crate) fn pending(&self) -> usize {
+        self.router.pending()
+    }
+
+    /// The number of responses dropped as no request was awaiting them.
+    pub(crate) fn unrouted(&self) -> u64 {
+        self.router.unrouted()
+    }
+
+    /// Waits for every request in flight to complete, then refuses any further requests.
+    pub(crate) async fn close(&self) {
+        self.in_flight.close().await;
+    }
+}
+
+pub(crate) async fn write(transport: &impl Exchange, function: WriteFunction) -> Result<(), Error> {
+    let ComposedMessage {
+        content, header, ..
+    } = transport.compose(|compositor| compositor.compose_write(&function))?;
+
+    let (response_header, packet) = transport.exchange(&content, &header).await?;
+
+    validate_response_header(&header, &response_header)?;
+    validate_response(transport, &header, &content, &packet).await
+}
+
+pub(crate) async fn read(
+    transport: &impl Exchange,
+    function: ReadFunction,
+) -> Result<LabJackDataValue, Error> {
+    let ComposedMessage {
+        content,
+        header,
+        expected_bytes,
+    } = transport.compose(|compositor| compositor.compose_read(&function))?;
+
+    let (response_header, packet) = transport.exchange(&content, &header).await?;
+    debug!("Response contains ... Header={response_header:?}. Packet={packet:?}");
+
+    validate_response_header(&header, &response_header)?;
+    validate_response(transport, &header, &content, &packet).await?;
+
+    let bytes = get_reply_data(&packet, expected_bytes)?;
+    debug!("Expected reply data: {bytes:?}");
+
+    // TODO: Check expected length and remove 1.. offset.
+    StandardDecoder { bytes }.decode_as(function.0.data_type)
+}
+
+pub(crate) async fn read_block(
+    transport: &impl Exchange,
+    function: ReadBlockFunction,
+) -> Result<Vec<LabJackDataValue>, Error> {
+    let ComposedMessage {
+        content,
+        header,
+        expected_bytes,
+    } = transport.compose(|compositor| compositor.compose_read_block(&function))?;
+
+    let (response_header, packet) = transport.exchange(&content, &header).await?;
+    debug!("Block response contains ... Header={response_header:?}. Packet={packet:?}");
+
+    validate_response_header(&header, &response_header)?;
+    validate_response(transport, &header, &content, &packet).await?;
+
+    let bytes = get_reply_data(&packet, expected_bytes)?;
+    decode_sequence(function.data_types, bytes)
+}
+
+pub(crate) async fn write_block(
+    transport: &impl Exchange,
+    function: WriteBlockFunction,
+) -> Result<(), Error> {
+    let ComposedMessage {
+        content, header, ..
+    } = transport.compose(|compositor| compositor.compose_write_block(&function))?;
+
+    let (response_header, packet) = transport.exchange(&content, &header).await?;
+
+    validate_response_header(&header, &response_header)?;
+    validate_response(transport, &header, &content, &packet).await
+}
+
+pub(crate) async fn feedback(
+    transport: &impl Exchange,
+    functions: &[FeedbackFunction],
+) -> Result<Vec<LabJackDataValue>, Error> {
+    let mut values = vec![];
+
+    for batch in partition_feedback(functions)? {
+        let ComposedMessage {
+            content,
+            header,
+            expected_bytes,
+        } = transport.compose(|compositor| compositor.compose_feedback(batch))?;
+
+        let (response_header, packet) = transport.exchange(&content, &header).await?;
+        debug!("Feedback response contains ... Header={response_header:?}. Packet={packet:?}");
+
+        validate_response_header(&header, &response_header)?;
+        validate_response(transport, &header, &content, &packet).await?;
+
+        let bytes = get_feedback_data(&packet, expected_bytes)?;
+        values.extend(decode_feedback(batch, bytes)?);
+    }
+
+    Ok(values)
+}
+
+/// Validates the response code, replacing a Modbus exception with the detailed
+/// [`LabJackErrorCode`] the device recorded for the request, where available.
+async fn validate_response(
+    transport: &impl Exchange,
+    header: &Header,
+    req: &[u8],
+    res: &[u8],
+) -> Result<(), Error> {
+    match validate_response_code(req, res) {
+        Err(Error::Exception(exception)) => {
+            Err(match last_error(transport, header.transaction_id).await {
+                Some(code) => Error::Device(code),
+                None => Error::Exception(exception),
+            })
+        }
+        result => result,
+    }
+}
+
+/// Reads the error last recorded by the device, provided it was raised by the request
+/// with the given transaction id. As requests may be pipelined, an error recorded for
+/// any other request is disregarded.
+async fn last_error(transport: &impl Exchange, transaction_id: u16) -> Option<LabJackErrorCode> {
+    let function = ReadBlockFunction::new(&[
+        *LAST_ERR_DETAIL,
+        *LAST_MB_ERR,
+        *LAST_ERR_FRAME,
+        *LAST_ERR_TRANSACTION_ID,
+    ])
+    .ok()?;
+
+    let ComposedMessage {
+        content,
+        header,
+        expected_bytes,
+    } = transport
+        .compose(|compositor| compositor.compose_read_block(&function))
+        .ok()?;
+
+    let (response_header, packet) = transport.exchange(&content, &header).await.ok()?;
+    validate_response_header(&header, &response_header).ok()?;
+    validate_response_code(&content, &packet).ok()?;
+
+    let bytes = get_reply_data(&packet, expected_bytes).ok()?;
+    match decode_sequence(function.data_types, bytes).ok()?.as_slice() {
+        [LabJackDataValue::Uint16(detail), _, _, LabJackDataValue::Uint16(id)]
+            if *detail != 0 && *id == transaction_id =>
+        {
+            debug!("Device recorded error {detail} for TxnID={transaction_id}");
+            LabJackErrorCode::from_code(u32::from(*detail))
+        }
+        _ => None,
+    }
+}
+
+fn validate_response_header(req: &Header, resp: &Header) -> Result<(), Error> {
+    if req.transaction_id != resp.transaction_id || resp.protocol_id != MODBUS_PROTOCOL_TCP {
+        Err(Error::InvalidResponse)
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_response_code(req: &[u8], res: &[u8]) -> Result<(), Error> {
+    let req_code = *req.get(7).ok_or(Error::InvalidResponse)?;
+    let res_code = *res.get(7).ok_or(Error::InvalidResponse)?;
+
+    match res_code {
+        code if code == req_code + 0x80 => {
+            let exception = *res.get(8).ok_or(Error::InvalidResponse)?;
+            match ExceptionCode::from_u8(exception) {
+                Some(code) => Err(Error::Exception(code)),
+                None => Err(Error::InvalidResponse),
+            }
+        }
+        code if code == req_code => Ok(()),
+        _ => Err(Error::InvalidResponse),
+    }
+}
+
+fn get_reply_data(reply: &[u8], expected_bytes: usize) -> Result<&[u8], Error> {
+    let given_response_length = *reply
+        .get(8)
+        .ok_or(Error::InvalidData(Reason::UnexpectedReplySize))?
+        as usize;
+    let reply_length_does_not_match = reply.len() != MODBUS_HEADER_SIZE + expected_bytes + 2;
+
+    if given_response_length != expected_bytes || reply_length_does_not_match {
+        return Err(Error::InvalidData(Reason::UnexpectedReplySize));
+    }
+
+    let reply_data = reply
+        .get(MODBUS_HEADER_SIZE + 2..)
+        .ok_or(Error::InvalidData(Reason::UnexpectedReplySize))?;
+
+    Ok(reply_data)
+}
+
+/// Feedback responses do not relay a byte count, instead the read-frame
+/// data directly follows the function code.
+fn get_feedback_data(reply: &[u8], expected_bytes: usize) -> Result<&[u8], Error> {
+    if reply.len() != MODBUS_HEADER_SIZE + expected_bytes + 1 {
+        return Err(Error::InvalidData(Reason::UnexpectedReplySize));
+    }
+
+    reply
+        .get(MODBUS_HEADER_SIZE + 1..)
+        .ok_or(Error::InvalidData(Reason::UnexpectedReplySize))
+}
@@ -1,30 +1,363 @@
 use crate::prelude::data_types::Register;
-use crate::prelude::LabJackDataValue;
+use crate::prelude::*;
 
 pub type Address = u16;
 pub type Quantity = u16;
 
+/// The Modbus Feedback (MBFB) function code, 76.
+///
+/// Referenced Documentation: [Modbus Feedback](https://support.labjack.com/docs/protocol-details-direct-modbus-tcp#ProtocolDetails[DirectModbusTCP]-ModbusFeedback(MBFB,function#76))
+pub const MODBUS_FEEDBACK_CODE: u8 = 0x4C;
+
 pub enum FeedbackFunction {
     ReadRegister(Register),
     WriteRegister(Register, LabJackDataValue),
 }
 
 impl FeedbackFunction {
+    /// Every frame begins with the frame type, the starting address and
+    /// the number of registers, irrespective of whether it reads or writes.
+    pub const FRAME_HEADER_SIZE: usize = 4;
+
     pub fn register(&self) -> Register {
         match self {
             FeedbackFunction::ReadRegister(r) => *r,
             FeedbackFunction::WriteRegister(r, _) => *r,
         }
     }
+
+    /// The number of bytes this frame occupies within the request.
+    /// Write-frames carry their data inline, after the frame header.
+    pub fn request_size(&self) -> usize {
+        match self {
+            FeedbackFunction::ReadRegister(..) => Self::FRAME_HEADER_SIZE,
+            FeedbackFunction::WriteRegister(reg, ..) => {
+                Self::FRAME_HEADER_SIZE + 2 * reg.data_type.size() as usize
+            }
+        }
+    }
+
+    /// The number of bytes this frame contributes to the response.
+    /// Only read-frames are given response data.
+    pub fn response_size(&self) -> usize {
+        match self {
+            FeedbackFunction::ReadRegister(reg) => 2 * reg.data_type.size() as usize,
+            FeedbackFunction::WriteRegister(..) => 0,
+        }
+    }
 }
 
 /// Write all registers corresponding to the entity, with given value.
-/// Must assert that the entity and value match register variants on types provided.
+/// The value is coerced to the data type of the register when written, see
+/// [`WriteFunction::value`].
 pub struct WriteFunction(pub Register, pub LabJackDataValue);
 
+impl WriteFunction {
+    /// The value to write, as the data type of the register.
+    pub fn value(&self) -> Result<LabJackDataValue, Error> {
+        self.1.coerce_to(self.0.data_type)
+    }
+}
+
 /// Read all registers corresponding to the entity.
 pub struct ReadFunction(pub Register);
 
+/// Read a contiguous block of registers, starting at `address`, in a single transaction.
+/// Each entry of `data_types` describes one LabJack value in the block, in address order.
+///
+/// This is the equivalent of LJM's `eReadAddresses` for adjacent registers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadBlockFunction {
+    pub address: Address,
+    pub data_types: Vec<LabJackDataType>,
+}
+
+/// Write a contiguous block of registers, starting at `address`, in a single transaction.
+/// Each entry of `values` is written to the register which follows the previous.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteBlockFunction {
+    pub address: Address,
+    pub values: Vec<LabJackDataValue>,
+}
+
+/// Asserts each register directly follows the one before it, returning the starting
+/// address of the block. A block running past the end of the address space is rejected.
+fn contiguous_start(registers: &[Register]) -> Result<Address, Error> {
+    let first = registers
+        .first()
+        .ok_or(Error::InvalidData(Reason::SendBufferEmpty))?;
+
+    registers.iter().try_fold(first.address, |next, register| {
+        if register.address != next {
+            return Err(Error::InvalidData(Reason::NonContiguousRegisters));
+        }
+
+        register
+            .address
+            .checked_add(register.data_type.size())
+            .ok_or(Error::InvalidData(Reason::NonContiguousRegisters))
+    })?;
+
+    Ok(first.address)
+}
+
+/// Determines the address of every value in a block which repeats the `start` register's
+/// data type, rejecting blocks which would run past the end of the address space.
+fn span_types(start: Register, count: Quantity) -> Result<Vec<LabJackDataType>, Error> {
+    if count < 1 {
+        return Err(Error::InvalidData(Reason::SendBufferEmpty));
+    }
+
+    start
+        .data_type
+        .size()
+        .checked_mul(count)
+        .and_then(|words| start.address.checked_add(words))
+        .ok_or(Error::InvalidData(Reason::NonContiguousRegisters))?;
+
+    Ok(vec![start.data_type; count as usize])
+}
+
+/// The number of bytes carried by each register of a byte buffer, see [`Register::buffer_width`].
+fn byte_buffer_width(register: Register) -> Result<usize, Error> {
+    if !register.metadata().is_buffer {
+        return Err(Error::InvalidData(Reason::NotABuffer));
+    }
+
+    register
+        .buffer_width()
+        .ok_or(Error::InvalidData(Reason::NotAByteBuffer))
+}
+
+/// The number of values of a buffer register which fit within a single request of
+/// `max_registers`, rejecting registers which are not buffers.
+fn buffer_values_per_packet(register: Register, max_registers: Quantity) -> Result<usize, Error> {
+    if !register.metadata().is_buffer {
+        return Err(Error::InvalidData(Reason::NotABuffer));
+    }
+
+    Ok((max_registers / register.data_type.size().max(1)) as usize)
+}
+
+/// Sums the sizes of a block's values, rejecting a block too large to be addressed.
+fn checked_quantity(mut sizes: impl Iterator<Item = Quantity>) -> Result<Quantity, Error> {
+    sizes
+        .try_fold(0, Quantity::checked_add)
+        .ok_or(Error::InvalidData(Reason::SendBufferTooBig))
+}
+
+impl ReadBlockFunction {
+    /// Creates a block read over a set of adjacent registers, which may differ in type.
+    pub fn new(registers: &[Register]) -> Result<ReadBlockFunction, Error> {
+        let function = ReadBlockFunction {
+            address: contiguous_start(registers)?,
+            data_types: registers.iter().map(|r| r.data_type).collect(),
+        };
+
+        function.validate()
+    }
+
+    /// Creates a block read of `count` values sharing the data type of the `start`
+    /// register. For example, `AIN0` with a count of 14 reads `AIN0` through `AIN13`.
+    pub fn span(start: Register, count: Quantity) -> Result<ReadBlockFunction, Error> {
+        let function = ReadBlockFunction {
+            address: start.address,
+            data_types: span_types(start, count)?,
+        };
+
+        function.validate()
+    }
+
+    /// Creates the read of a single value of the given type at `address`, for registers
+    /// known only by address, such as those of a [`RegisterMap`].
+    pub fn at(address: Address, data_type: LabJackDataType) -> Result<ReadBlockFunction, Error> {
+        address
+            .checked_add(data_type.size())
+            .ok_or(Error::InvalidData(Reason::NonContiguousRegisters))?;
+
+        let function = ReadBlockFunction {
+            address,
+            data_types: vec![data_type],
+        };
+
+        function.validate()
+    }
+
+    /// Creates the reads of `len` bytes from a buffer register. Each reads the same address
+    /// repeatedly, yielding successive contents of the buffer, and is split such that it
+    /// fits within a single packet. See [`Register::buffer_width`] for how bytes are carried.
+    pub fn buffer(register: Register, len: usize) -> Result<Vec<ReadBlockFunction>, Error> {
+        let width = byte_buffer_width(register)?;
+
+        let words = vec![LabJackDataType::Uint16; len.div_ceil(width)];
+        Ok(words
+            .chunks(MODBUS_MAX_READ_REGISTERS as usize)
+            .map(|chunk| ReadBlockFunction {
+                address: register.address,
+                data_types: chunk.to_vec(),
+            })
+            .collect())
+    }
+
+    /// Creates the reads of `count` values from a buffer register of words, such as
+    /// `USER_RAM_FIFO0_DATA_U32`, each value being of the register's data type. As with
+    /// [`ReadBlockFunction::buffer`], each reads the same address repeatedly.
+    pub fn buffer_values(
+        register: Register,
+        count: usize,
+    ) -> Result<Vec<ReadBlockFunction>, Error> {
+        let per_packet = buffer_values_per_packet(register, MODBUS_MAX_READ_REGISTERS)?;
+
+        let values = vec![register.data_type; count];
+        Ok(values
+            .chunks(per_packet)
+            .map(|chunk| ReadBlockFunction {
+                address: register.address,
+                data_types: chunk.to_vec(),
+            })
+            .collect())
+    }
+
+    /// The total number of modbus registers (words) spanned by the block.
+    pub fn quantity(&self) -> Result<Quantity, Error> {
+        checked_quantity(self.data_types.iter().map(LabJackDataType::size))
+    }
+
+    fn validate(self) -> Result<ReadBlockFunction, Error> {
+        if self.quantity()? > MODBUS_MAX_READ_REGISTERS {
+            return Err(Error::InvalidData(Reason::SendBufferTooBig));
+        }
+
+        Ok(self)
+    }
+}
+
+impl WriteBlockFunction {
+    /// Creates a block write over a set of adjacent registers. Each value is coerced
+    /// to the data type of the register it is written to.
+    pub fn new(
+        registers: &[Register],
+        values: &[LabJackDataValue],
+    ) -> Result<WriteBlockFunction, Error> {
+        if registers.len() != values.len() {
+            return Err(Error::InvalidData(Reason::RegisterMismatch));
+        }
+
+        let values = registers
+            .iter()
+            .zip(values)
+            .map(|(register, value)| value.coerce_to(register.data_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let function = WriteBlockFunction {
+            address: contiguous_start(registers)?,
+            values,
+        };
+
+        function.validate()
+    }
+
+    /// Creates a block write of each value into consecutive registers sharing the
+    /// data type of the `start` register, to which each value is coerced.
+    pub fn span(start: Register, values: &[LabJackDataValue]) -> Result<WriteBlockFunction, Error> {
+        let count = Quantity::try_from(values.len())
+            .map_err(|_| Error::InvalidData(Reason::SendBufferTooBig))?;
+        span_types(start, count)?;
+
+        let values = values
+            .iter()
+            .map(|value| value.coerce_to(start.data_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let function = WriteBlockFunction {
+            address: start.address,
+            values,
+        };
+
+        function.validate()
+    }
+
+    /// Creates the write of a single value at `address`, coerced to the given type, for
+    /// registers known only by address, such as those of a [`RegisterMap`].
+    pub fn at(
+        address: Address,
+        data_type: LabJackDataType,
+        value: LabJackDataValue,
+    ) -> Result<WriteBlockFunction, Error> {
+        address
+            .checked_add(data_type.size())
+            .ok_or(Error::InvalidData(Reason::NonContiguousRegisters))?;
+
+        let function = WriteBlockFunction {
+            address,
+            values: vec![value.coerce_to(data_type)?],
+        };
+
+        function.validate()
+    }
+
+    /// Creates the writes of `bytes` into a buffer register, each writing the same address
+    /// repeatedly, split such that each fits within a single packet. A buffer packing two
+    /// bytes per register is padded with a null byte where given an odd number.
+    pub fn buffer(register: Register, bytes: &[u8]) -> Result<Vec<WriteBlockFunction>, Error> {
+        let width = byte_buffer_width(register)?;
+
+        let words = bytes
+            .chunks(width)
+            .map(|word| match word {
+                [high, low] => LabJackDataValue::Uint16(u16::from_be_bytes([*high, *low])),
+                [high] if width == 2 => LabJackDataValue::Uint16(u16::from_be_bytes([*high, 0])),
+                [low] => LabJackDataValue::Uint16(u16::from(*low)),
+                _ => LabJackDataValue::Uint16(0),
+            })
+            .collect::<Vec<_>>();
+
+        Ok(words
+            .chunks(MODBUS_MAX_WRITE_REGISTERS as usize)
+            .map(|chunk| WriteBlockFunction {
+                address: register.address,
+                values: chunk.to_vec(),
+            })
+            .collect())
+    }
+
+    /// Creates the writes of `values` into a buffer register of words, such as
+    /// `USER_RAM_FIFO0_DATA_U32`, each value coerced to the register's data type. As with
+    /// [`WriteBlockFunction::buffer`], each writes the same address repeatedly.
+    pub fn buffer_values(
+        register: Register,
+        values: &[LabJackDataValue],
+    ) -> Result<Vec<WriteBlockFunction>, Error> {
+        let per_packet = buffer_values_per_packet(register, MODBUS_MAX_WRITE_REGISTERS)?;
+
+        let values = values
+            .iter()
+            .map(|value| value.coerce_to(register.data_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(values
+            .chunks(per_packet)
+            .map(|chunk| WriteBlockFunction {
+                address: register.address,
+                values: chunk.to_vec(),
+            })
+            .collect())
+    }
+
+    /// The total number of modbus registers (words) spanned by the block.
+    pub fn quantity(&self) -> Result<Quantity, Error> {
+        checked_quantity(self.values.iter().map(|v| v.r#type().size()))
+    }
+
+    fn validate(self) -> Result<WriteBlockFunction, Error> {
+        if self.quantity()? > MODBUS_MAX_WRITE_REGISTERS {
+            return Err(Error::InvalidData(Reason::SendBufferTooBig));
+        }
+
+        Ok(self)
+    }
+}
+
 pub trait Function {
     fn code(&self) -> u8;
 }
@@ -41,6 +374,18 @@ impl Function for WriteFunction {
     }
 }
 
+impl Function for ReadBlockFunction {
+    fn code(&self) -> u8 {
+        0x03
+    }
+}
+
+impl Function for WriteBlockFunction {
+    fn code(&self) -> u8 {
+        0x10 // 16
+    }
+}
+
 impl Function for FeedbackFunction {
     fn code(&self) -> u8 {
         match *self {
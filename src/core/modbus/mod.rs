@@ -2,6 +2,7 @@ pub mod binary;
 pub mod client;
 pub mod composite;
 pub mod error;
+pub(crate) mod exchange;
 pub mod function;
 pub mod transport;
 pub mod transports;
@@ -15,6 +15,32 @@ pub trait Transport: Debug {
         function: ReadFunction,
     ) -> impl std::future::Future<Output = Result<LabJackDataValue, Self::Error>> + Send;
 
-    // TODO: Return type should be feedback values not bytes
-    // fn feedback(&mut self, data: &[FeedbackFunction]) -> Result<Box<[u8]>, Self::Error>;
+    /// Reads a contiguous block of registers in one transaction, returning
+    /// one value per entry of the block.
+    fn read_block(
+        &mut self,
+        function: ReadBlockFunction,
+    ) -> impl std::future::Future<Output = Result<Vec<LabJackDataValue>, Self::Error>> + Send;
+
+    /// Writes a contiguous block of registers in one transaction.
+    fn write_block(
+        &mut self,
+        function: WriteBlockFunction,
+    ) -> impl std::future::Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Performs a set of reads and writes using Modbus Feedback (MBFB). Returns one
+    /// value for each read-frame, in the order given. Write-frames are acknowledged,
+    /// but do not produce a value.
+    ///
+    /// Sets too large for a single packet are split across several transactions.
+    fn feedback(
+        &mut self,
+        functions: &[FeedbackFunction],
+    ) -> impl std::future::Future<Output = Result<Vec<LabJackDataValue>, Self::Error>> + Send;
+
+    /// Completes any outstanding requests and releases the resources held by the
+    /// transport, such as its connection and background tasks.
+    fn close(&mut self) -> impl std::future::Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::Duration;
 
 use crate::prelude::*;
@@ -19,46 +20,110 @@ impl EmulatedValue {
     }
 }
 
-#[derive(Debug)]
+/// An in-memory stand-in for a device. Like [`TcpTransport`], clones are handles
+/// onto the same device, and so observe each other's writes.
+#[derive(Clone, Debug)]
 pub struct EmulatedTransport {
-    addresses: HashMap<Address, EmulatedValue>,
+    addresses: Arc<Mutex<HashMap<Address, EmulatedValue>>>,
     pub device: LabJackDevice,
 }
 
 impl EmulatedTransport {
     fn new(device: LabJackDevice) -> EmulatedTransport {
         EmulatedTransport {
-            addresses: HashMap::new(),
+            addresses: Arc::new(Mutex::new(HashMap::new())),
             device,
         }
     }
+
+    fn addresses(&self) -> MutexGuard<'_, HashMap<Address, EmulatedValue>> {
+        self.addresses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl EmulatedTransport {
+    fn value_at(
+        &self,
+        address: Address,
+        data_type: LabJackDataType,
+    ) -> Result<LabJackDataValue, Error> {
+        let EmulatedValue {
+            base: value,
+            function: _,
+        } = self
+            .addresses()
+            .get(&address)
+            .cloned()
+            .unwrap_or(EmulatedValue::transparent(data_type.floating()));
+
+        EmulatedDecoder { value }.decode_as(data_type)
+    }
 }
 
 impl Transport for EmulatedTransport {
     type Error = Error;
 
     async fn write(&mut self, function: WriteFunction) -> Result<(), Self::Error> {
-        self.addresses
-            .insert(function.0.address, EmulatedValue::transparent(function.1));
+        let value = function.value()?;
+        self.addresses()
+            .insert(function.0.address, EmulatedValue::transparent(value));
         Ok(())
     }
 
     async fn read(&mut self, function: ReadFunction) -> Result<LabJackDataValue, Self::Error> {
-        let EmulatedValue {
-            base: value,
-            function: _,
-        } = self
-            .addresses
-            .get(&function.0.address)
-            .cloned()
-            .unwrap_or(EmulatedValue::transparent(function.0.data_type.floating()));
+        self.value_at(function.0.address, function.0.data_type)
+    }
+
+    async fn read_block(
+        &mut self,
+        function: ReadBlockFunction,
+    ) -> Result<Vec<LabJackDataValue>, Self::Error> {
+        let mut address = function.address;
+
+        function
+            .data_types
+            .iter()
+            .map(|data_type| {
+                let value = self.value_at(address, *data_type);
+                address += data_type.size();
+                value
+            })
+            .collect()
+    }
 
-        EmulatedDecoder { value }.decode_as(function.0.data_type)
+    async fn write_block(&mut self, function: WriteBlockFunction) -> Result<(), Self::Error> {
+        let mut address = function.address;
+
+        for value in function.values {
+            self.addresses()
+                .insert(address, EmulatedValue::transparent(value));
+            address += value.r#type().size();
+        }
+
+        Ok(())
     }
 
-    // fn feedback(&mut self, data: &[FeedbackFunction]) -> Result<Box<[u8]>, Self::Error> {
-    //     todo!()
-    // }
+    async fn feedback(
+        &mut self,
+        functions: &[FeedbackFunction],
+    ) -> Result<Vec<LabJackDataValue>, Self::Error> {
+        let mut values = vec![];
+
+        for function in functions {
+            match function {
+                FeedbackFunction::ReadRegister(register) => {
+                    values.push(self.read(ReadFunction(*register)).await?);
+                }
+                FeedbackFunction::WriteRegister(register, value) => {
+                    self.write(WriteFunction(*register, *value)).await?;
+                }
+            }
+        }
+
+        Ok(values)
+    }
 }
 
 pub struct Emulated;
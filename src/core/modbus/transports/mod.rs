@@ -1,5 +1,7 @@
 pub mod emulated;
 pub mod tcp;
+pub mod udp;
 
 pub use emulated::*;
 pub use tcp::*;
+pub use udp::*;
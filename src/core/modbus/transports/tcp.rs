@@ -1,19 +1,18 @@
-use crate::prelude::data_types::StandardDecoder;
+use crate::core::modbus::exchange::{self, Exchange, Pipeline};
 use crate::prelude::*;
-use enum_primitive::FromPrimitive;
 use futures_util::sink::SinkExt;
 use log::{debug, error, trace, warn};
-use std::collections::HashSet;
 use std::io::Write;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::core::Error::Io;
-use crate::prelude::Decoder as LocalDecoder;
-use crate::queue::buffer::Topic;
+use crate::queue::listener::ListenerHandle;
+use crate::queue::router::Router;
+use socket2::{SockRef, TcpKeepalive};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
-use tokio::net::TcpStream;
-use tokio::sync::{Mutex, Notify};
+use tokio::net::{TcpSocket, TcpStream};
+use tokio::sync::{watch, Mutex, Notify};
 use tokio_stream::StreamExt;
 use tokio_util::bytes::{Buf, BufMut, BytesMut};
 use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite};
@@ -40,8 +39,11 @@ pub const MAX_DATA_LENGTH: usize = 1040;
 /// the UnitID field is not used (as bridging is not used). Therefore, the default
 /// value is suggested to be the u8 literal, 1. Alternatively, `0b00000001`.
 ///
+/// The unit id may be overridden through the [`ConnectOptions`], for devices reached
+/// through a Modbus gateway.
+///
 /// Referenced Documentation: [LabJack Modbus Protocol Details: Fields](https://support.labjack.com/docs/protocol-details-direct-modbus-tcp#ProtocolDetails[DirectModbusTCP]-Fields).
-const BASE_UNIT_ID: u8 = 1;
+pub const BASE_UNIT_ID: u8 = 1;
 
 /// The base transaction ID. We use this value to identify a unique transaction,
 /// such that the LabJack will relay this value back to us.
@@ -51,65 +53,247 @@ const BASE_UNIT_ID: u8 = 1;
 /// for each new message.
 ///
 /// Referenced Documentation: [LabJack Modbus Protocol Details: Fields](https://support.labjack.com/docs/protocol-details-direct-modbus-tcp#ProtocolDetails[DirectModbusTCP]-Fields).
-const STARTING_TRANSACTION_ID: u16 = 0;
+pub(crate) const STARTING_TRANSACTION_ID: u16 = 0;
 
-// TODO: Redo the responsibilities of the transaction id here...
+/// The default duration a request may take, from being sent until its response
+/// is received, before failing with [`Error::Timeout`]. This is the analogue of
+/// LJM's `LJM_SEND_RECEIVE_TIMEOUT_MS` configuration, and may be changed with
+/// [`TcpTransport::set_timeout`].
+pub const DEFAULT_SEND_RECEIVE_TIMEOUT: Duration = Duration::from_millis(2600);
 
-#[derive(Debug)]
+/// The default number of requests which may be awaiting a response at once, across
+/// every clone of a [`TcpTransport`]. Kept conservative so as not to exceed the
+/// command buffer of the device firmware, and may be changed with
+/// [`TcpTransport::set_max_in_flight`].
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 4;
+
+/// A Modbus TCP connection to a LabJack device.
+///
+/// The transport is a cheaply cloneable handle. Every clone shares the same socket,
+/// and as responses are routed by transaction id, requests from each clone are
+/// pipelined over the connection rather than serialised. The number of requests
+/// in flight at once is bounded by [`TcpTransport::set_max_in_flight`].
+#[derive(Debug, Clone)]
 pub struct TcpTransport {
-    transaction_id: u16,
-    unit_id: u8,
+    pipeline: Pipeline,
+
+    listener: Arc<ListenerHandle>,
+    stream_write: Arc<Mutex<Option<StreamWrite>>>,
+    state: watch::Sender<ConnectionState>,
 
+    timeout: Duration,
+}
+
+type StreamRead = FramedRead<OwnedReadHalf, BytesCodec>;
+type StreamWrite = FramedWrite<OwnedWriteHalf, BytesCodec>;
+
+/// Owns the read half of the connection, routing responses to their requests. When the
+/// connection is lost, it fails every in-flight request and re-establishes the connection
+/// according to its [`Backoff`], publishing each change of [`ConnectionState`].
+struct Listener {
+    router: Arc<Router>,
     cancel: Arc<Notify>,
-    stream_write: Arc<Mutex<FramedWrite<OwnedWriteHalf, BytesCodec>>>,
-    topic: Arc<Topic>,
-
-    /// A hashset of existing transactions to indicate which values
-    /// the transaction_id can take. When it's length is equal to
-    /// [`u16::MAX`], no more transactions can be made. It is key
-    /// that upon the completion of a transaction, it's identifier
-    /// is removed from this set.
-    existing_transactions: HashSet<u16>,
+    stream_write: Arc<Mutex<Option<StreamWrite>>>,
+    state: watch::Sender<ConnectionState>,
+
+    /// The peer to reconnect to. If unknown, the connection is not re-established.
+    address: Option<SocketAddr>,
+    /// Applied to each new connection as it is re-established, which
+    /// is attempted according to their [`Backoff`].
+    options: ConnectOptions,
 }
 
 impl TcpTransport {
     pub fn new(stream: TcpStream) -> TcpTransport {
+        TcpTransport::with_backoff(stream, Backoff::default())
+    }
+
+    /// Creates the transport over an established stream. Should the connection be lost,
+    /// it is re-established to the same peer according to the given [`Backoff`].
+    pub fn with_backoff(stream: TcpStream, backoff: Backoff) -> TcpTransport {
+        let options = ConnectOptions {
+            backoff,
+            ..ConnectOptions::default()
+        };
+
+        TcpTransport::with_options(stream, options)
+    }
+
+    /// Creates the transport over an established stream, applying the `options`
+    /// to any connection which replaces it, and reconnecting by their [`Backoff`].
+    fn with_options(stream: TcpStream, options: ConnectOptions) -> TcpTransport {
+        let address = stream.peer_addr().ok();
         let (read, write) = stream.into_split();
-        let fr = FramedRead::new(read, BytesCodec);
-        let fw = FramedWrite::new(write, BytesCodec);
 
-        let topic = Topic::new();
+        let pipeline = Pipeline::new(options.unit_id);
         let notify = Arc::new(Notify::new());
+        let stream_write = Arc::new(Mutex::new(Some(FramedWrite::new(write, BytesCodec))));
+        let (state, _) = watch::channel(ConnectionState::Connected);
 
-        let listener_topic = Arc::clone(&topic);
-        let listener_notify = Arc::clone(&notify);
+        let listener = Listener {
+            router: pipeline.router(),
+            cancel: Arc::clone(&notify),
+            stream_write: Arc::clone(&stream_write),
+            state: state.clone(),
+            address,
+            options,
+        };
 
-        tokio::spawn(async move {
-            TcpTransport::listen(
-                Arc::clone(&listener_topic),
-                Arc::clone(&listener_notify),
-                fr,
-            )
-            .await
-        });
+        let task = tokio::spawn(listener.run(FramedRead::new(read, BytesCodec)));
 
         TcpTransport {
-            unit_id: BASE_UNIT_ID,
-            transaction_id: STARTING_TRANSACTION_ID,
+            pipeline,
+
+            listener: Arc::new(ListenerHandle::new(notify, task)),
+            stream_write,
+            state,
+
+            timeout: DEFAULT_SEND_RECEIVE_TIMEOUT,
+        }
+    }
+
+    /// Subscribes to changes in the [`ConnectionState`] of the transport.
+    ///
+    /// ```
+    /// use labjack::prelude::*;
+    ///
+    /// # async fn docs(transport: TcpTransport) {
+    /// let mut state = transport.state();
+    /// while state.changed().await.is_ok() {
+    ///     println!("Connection is now {}", *state.borrow());
+    /// }
+    /// # }
+    /// ```
+    pub fn state(&self) -> watch::Receiver<ConnectionState> {
+        self.state.subscribe()
+    }
+
+    /// Sets the number of requests which may be awaiting a response at once. Further
+    /// requests wait for an earlier one to complete before being sent.
+    ///
+    /// The limit is shared by every clone of this handle. Lowering it waits for
+    /// enough of the requests in flight to complete.
+    pub async fn set_max_in_flight(&self, max_in_flight: usize) {
+        self.pipeline.set_max_in_flight(max_in_flight).await;
+    }
+
+    /// The number of requests awaiting a response.
+    pub fn pending_requests(&self) -> usize {
+        self.pipeline.pending()
+    }
+
+    /// The number of responses received which matched no pending request, and so
+    /// were dropped. For example, a response which arrives after its request timed out.
+    pub fn unrouted_responses(&self) -> u64 {
+        self.pipeline.unrouted()
+    }
+
+    /// The duration a request may take to be sent and answered.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets the duration a request may take to be sent and answered, after which
+    /// it fails with [`Error::Timeout`] and its pending response is discarded.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+}
+
+impl Exchange for TcpTransport {
+    fn compose<F>(&self, compose: F) -> Result<ComposedMessage, Error>
+    where
+        F: FnOnce(&mut Compositor) -> Result<ComposedMessage, Error>,
+    {
+        self.pipeline.compose(compose)
+    }
+
+    /// Sends a composed message and awaits the response with the same transaction id.
+    /// Both steps are bounded by the transport's timeout.
+    ///
+    /// The response slot is reserved before the message is sent, so a fast reply cannot
+    /// be missed, and is released however the exchange ends. Time spent waiting for
+    /// an in-flight permit does not count toward the timeout.
+    async fn exchange(&self, content: &[u8], header: &Header) -> Result<(Header, Vec<u8>), Error> {
+        let transaction_id = header.transaction_id;
+
+        let (_permit, mut slot) = self.pipeline.reserve(transaction_id).await?;
+
+        let exchange = async {
+            match self.stream_write.lock().await.as_mut() {
+                Some(stream) => stream.send(content.to_vec()).await?,
+                None => return Err(Error::Disconnected),
+            }
+
+            slot.response().await.map_err(|err| match err {
+                Error::Queue(QueueError::SlotClosed) => Error::Disconnected,
+                err => err,
+            })
+        };
+
+        match tokio::time::timeout(self.timeout, exchange).await {
+            Ok(response) => response,
+            Err(_) => {
+                warn!(
+                    "Request timed out after {:?}. TxnID={transaction_id}",
+                    self.timeout
+                );
+                Err(Error::Timeout)
+            }
+        }
+    }
+}
+
+impl Transport for TcpTransport {
+    type Error = Error;
 
-            cancel: notify,
-            stream_write: Arc::new(Mutex::new(fw)),
+    /// Waits for every request in flight to complete, then stops the listener and
+    /// shuts down the connection. Requests made afterwards, from this handle or its
+    /// clones, fail with [`Error::Closed`].
+    async fn close(&mut self) -> Result<(), Self::Error> {
+        self.pipeline.close().await;
 
-            topic: Arc::clone(&topic),
-            existing_transactions: HashSet::new(),
+        let stream = self.stream_write.lock().await.take();
+
+        self.listener.stop().await;
+
+        if let Some(mut stream) = stream {
+            stream.close().await?;
         }
+
+        debug!("Transport closed.");
+        Ok(())
+    }
+
+    async fn write(&mut self, function: WriteFunction) -> Result<(), Self::Error> {
+        exchange::write(self, function).await
+    }
+
+    async fn read(&mut self, function: ReadFunction) -> Result<LabJackDataValue, Self::Error> {
+        exchange::read(self, function).await
     }
 
-    async fn listen(
-        topic: Arc<Topic>,
-        notify: Arc<Notify>,
-        mut read: FramedRead<OwnedReadHalf, BytesCodec>,
-    ) {
+    async fn read_block(
+        &mut self,
+        function: ReadBlockFunction,
+    ) -> Result<Vec<LabJackDataValue>, Self::Error> {
+        exchange::read_block(self, function).await
+    }
+
+    async fn write_block(&mut self, function: WriteBlockFunction) -> Result<(), Self::Error> {
+        exchange::write_block(self, function).await
+    }
+
+    async fn feedback(
+        &mut self,
+        functions: &[FeedbackFunction],
+    ) -> Result<Vec<LabJackDataValue>, Self::Error> {
+        exchange::feedback(self, functions).await
+    }
+}
+
+impl Listener {
+    async fn run(self, mut read: StreamRead) {
         loop {
             tokio::select! {
                 data = read.next() => {
@@ -121,137 +305,72 @@ impl TcpTransport {
                                 header.transaction_id
                             );
 
-                            // Publish the packet through to the subscriber
-                            topic.publish(header, packet).await;
-                        }
-                        Some(Err(Io(err))) if err.raw_os_error().is_some_and(|i| i == 54) => {
-                            warn!("Port Closed.")
+                            // Route the packet through to the pending request
+                            self.router.publish(header, packet);
+                            continue;
                         }
                         Some(Err(err)) => {
                             error!("Error reading from `BytesCodec` stream: {:?}", err);
                         }
-                        _ => {}
+                        None => {
+                            warn!("Port Closed.");
+                        }
                     }
                 }
-                _ = notify.notified() => {
+                _ = self.cancel.notified() => {
                     break
                 }
             }
-        }
-
-        debug!("Listening ended, cancellation notice issued.")
-    }
-
-    fn compositor(&mut self) -> Compositor {
-        Compositor {
-            transaction_id: &mut self.transaction_id,
-            unit_id: self.unit_id,
-        }
-    }
-
-    fn validate_response_header(req: &Header, resp: &Header) -> Result<(), Error> {
-        if req.transaction_id != resp.transaction_id || resp.protocol_id != MODBUS_PROTOCOL_TCP {
-            Err(Error::InvalidResponse)
-        } else {
-            Ok(())
-        }
-    }
 
-    fn validate_response_code(req: &[u8], res: &[u8]) -> Result<(), Error> {
-        let req_code = *req.get(7).ok_or(Error::InvalidResponse)?;
-        let res_code = *res.get(7).ok_or(Error::InvalidResponse)?;
+            // The connection is lost, so no response will arrive for requests in flight.
+            self.stream_write.lock().await.take();
+            self.router.close_all();
+            self.state.send_replace(ConnectionState::Reconnecting);
 
-        match res_code {
-            code if code == req_code + 0x80 => {
-                let exception = *res.get(8).ok_or(Error::InvalidResponse)?;
-                match ExceptionCode::from_u8(exception) {
-                    Some(code) => Err(Error::Exception(code)),
-                    None => Err(Error::InvalidResponse),
-                }
+            match self.reconnect().await {
+                Some(stream) => read = stream,
+                None => break,
             }
-            code if code == req_code => Ok(()),
-            _ => Err(Error::InvalidResponse),
-        }
-    }
-
-    fn get_reply_data(reply: &[u8], expected_bytes: usize) -> Result<&[u8], Error> {
-        let given_response_length = *reply
-            .get(8)
-            .ok_or(Error::InvalidData(Reason::UnexpectedReplySize))?
-            as usize;
-        let reply_length_does_not_match = reply.len() != MODBUS_HEADER_SIZE + expected_bytes + 2;
-
-        if given_response_length != expected_bytes || reply_length_does_not_match {
-            return Err(Error::InvalidData(Reason::UnexpectedReplySize));
         }
 
-        let reply_data = reply
-            .get(MODBUS_HEADER_SIZE + 2..)
-            .ok_or(Error::InvalidData(Reason::UnexpectedReplySize))?;
+        self.stream_write.lock().await.take();
+        self.router.close_all();
+        self.state.send_replace(ConnectionState::Closed);
 
-        Ok(reply_data)
+        debug!("Listening ended, connection closed.")
     }
-}
 
-impl Transport for TcpTransport {
-    type Error = Error;
+    /// Attempts to re-establish the connection, returning the new read half once
+    /// connected. Returns `None` once attempts are exhausted, or on cancellation.
+    async fn reconnect(&self) -> Option<StreamRead> {
+        let address = self.address?;
+        let mut attempts = 0;
 
-    async fn write(&mut self, function: WriteFunction) -> Result<(), Self::Error> {
-        let ComposedMessage { content, .. } = self.compositor().compose_write(&function)?;
+        while self.options.backoff.permits(attempts) {
+            let delay = self.options.backoff.delay(attempts);
+            attempts += 1;
 
-        self.stream_write.lock().await.send(content.clone()).await?;
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = self.cancel.notified() => return None,
+            }
 
-        let (header, packet) = self.topic.wait_on(self.transaction_id).await?;
-        let response_header = Header::unpack(packet.as_slice())?;
+            debug!("Reconnecting to {address}, attempt {attempts}.");
+            match dial(address, &self.options).await {
+                Ok(stream) => {
+                    let (read, write) = stream.into_split();
+                    *self.stream_write.lock().await = Some(FramedWrite::new(write, BytesCodec));
+                    self.state.send_replace(ConnectionState::Connected);
 
-        TcpTransport::validate_response_header(&header, &response_header)?;
-        TcpTransport::validate_response_code(&content, packet.as_slice())
-    }
+                    debug!("Reconnected to {address}.");
+                    return Some(FramedRead::new(read, BytesCodec));
+                }
+                Err(err) => warn!("Failed to reconnect to {address}: {err}"),
+            }
+        }
 
-    async fn read(&mut self, function: ReadFunction) -> Result<LabJackDataValue, Self::Error> {
-        let ComposedMessage {
-            content,
-            header,
-            expected_bytes,
-        } = self.compositor().compose_read(&function)?;
-
-        // self.stream_write.lock().await.
-        self.stream_write.lock().await.send(content.clone()).await?;
-
-        // We make a copy of the TID so it is not modified whilst in use
-        let (response_header, packet) = self.topic.wait_on(self.transaction_id).await?;
-        debug!("Response contains ... Header={response_header:?}. Packet={packet:?}");
-
-        TcpTransport::validate_response_header(&header, &response_header)?;
-        TcpTransport::validate_response_code(&content, &packet)?;
-
-        let bytes = TcpTransport::get_reply_data(&packet, expected_bytes)?;
-        debug!("Expected reply data: {bytes:?}");
-
-        // TODO: Check expected length and remove 1.. offset.
-        StandardDecoder { bytes }.decode_as(function.0.data_type)
-    }
-
-    // fn feedback(&mut self, data: &[FeedbackFunction]) -> Result<Box<[u8]>, Self::Error> {
-    //     let ComposedMessage {
-    //         content,
-    //         header,
-    //         expected_bytes,
-    //     } = self.compositor().compose_feedback(data)?;
-    //     let mut reply = vec![0; MODBUS_HEADER_SIZE + expected_bytes + 2].into_boxed_slice();
-    //
-    //     self.stream.write_all(&content).map_err(Error::Io)?;
-    //     self.stream.read(&mut reply).map_err(Error::Io)?;
-    //
-    //     let reply_header_raw = &reply
-    //         .get(..MODBUS_HEADER_SIZE)
-    //         .ok_or(Error::InvalidResponse)?;
-    //     let resp_hd = Header::unpack(reply_header_raw)?;
-    //
-    //     TcpTransport::validate_response_header(&header, &resp_hd)?;
-    //     TcpTransport::validate_response_code(&content, &reply)?;
-    //     TcpTransport::get_reply_data(&reply, expected_bytes).map(Box::from)
-    // }
+        None
+    }
 }
 
 /// The TCP ModBus client.
@@ -277,13 +396,48 @@ impl Connect for Tcp {
     type Transport = TcpTransport;
 
     async fn connect(device: LabJackDevice) -> Result<Self::Transport, Error> {
-        let addr = SocketAddr::new(device.ip_address, MODBUS_COMMUNICATION_PORT);
-        let stream = TcpStream::connect(addr).await.map_err(Error::Io)?;
+        Tcp::connect_with_options(device, ConnectOptions::default()).await
+    }
 
-        Ok(TcpTransport::new(stream))
+    async fn connect_with_options(
+        device: LabJackDevice,
+        options: ConnectOptions,
+    ) -> Result<Self::Transport, Error> {
+        let stream = dial(options.address(&device), &options).await?;
+        Ok(TcpTransport::with_options(stream, options))
     }
 }
 
+/// Establishes a connection to the `address`, configured according to the `options`.
+async fn dial(address: SocketAddr, options: &ConnectOptions) -> Result<TcpStream, Error> {
+    let socket = match address {
+        SocketAddr::V4(_) => TcpSocket::new_v4(),
+        SocketAddr::V6(_) => TcpSocket::new_v6(),
+    }?;
+
+    if let Some(local_address) = options.local_address {
+        socket.bind(local_address)?;
+    }
+
+    socket.set_nodelay(options.nodelay)?;
+    if let Some(keepalive) = options.keepalive {
+        let keepalive = TcpKeepalive::new()
+            .with_time(keepalive)
+            .with_interval(keepalive);
+        SockRef::from(&socket).set_tcp_keepalive(&keepalive)?;
+    }
+
+    let stream = match options.connect_timeout {
+        Some(timeout) => tokio::time::timeout(timeout, socket.connect(address))
+            .await
+            .map_err(|_| Error::Timeout)?,
+        None => socket.connect(address).await,
+    }?;
+
+    debug!("Connected to {address}.");
+    Ok(stream)
+}
+
 #[derive(Debug)]
 struct BytesCodec;
 
@@ -346,13 +500,19 @@ impl Encoder<Vec<u8>> for BytesCodec {
 mod test {
     use log::debug;
     use std::time::Duration;
-    use tokio::io::AsyncWriteExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::join;
     use tokio::net::{TcpListener, TcpStream};
     use tokio::time::sleep;
 
-    use crate::core::{LabJackDataValue, ReadFunction};
-    use crate::prelude::{TcpTransport, Transport, TEST_UINT32};
+    use crate::core::{
+        Error, ExceptionCode, FeedbackFunction, LabJackDataValue, ReadBlockFunction, ReadFunction,
+    };
+    use crate::prelude::{
+        Backoff, Connect, ConnectOptions, ConnectionState, DeviceType, LabJackDevice,
+        LabJackErrorCode, LabJackString, Tcp, TcpTransport, Transport, DEVICE_NAME_DEFAULT,
+        TEST_UINT16, TEST_UINT32,
+    };
 
     async fn setup() -> (TcpTransport, TcpStream) {
         env_logger::try_init().ok();
@@ -385,7 +545,7 @@ mod test {
         let join = tokio::spawn(async move {
             sleep(Duration::from_millis(100)).await;
             writer
-                .write(&[
+                .write_all(&[
                     0x00, 0x01, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x11, 0x22, 0x33,
                 ])
                 .await
@@ -399,10 +559,12 @@ mod test {
                 .await
                 .expect("Must write read fn.");
             assert_eq!(value, LabJackDataValue::Uint32(0x00112233));
-            transport.cancel.notify_one();
+            transport.close().await.expect("Must close");
         });
 
-        join!(join2, join);
+        let (reader, writer) = join!(join2, join);
+        reader.expect("Reader task must complete");
+        writer.expect("Writer task must complete");
     }
 
     #[tokio::test]
@@ -413,7 +575,7 @@ mod test {
             sleep(Duration::from_millis(100)).await;
 
             writer
-                .write(&[
+                .write_all(&[
                     0x00, 0x01, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x11, 0x22, 0x44,
                 ])
                 .await
@@ -422,7 +584,7 @@ mod test {
             sleep(Duration::from_millis(100)).await;
 
             writer
-                .write(&[
+                .write_all(&[
                     0x00, 0x02, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x11, 0x22, 0x33,
                 ])
                 .await
@@ -431,7 +593,7 @@ mod test {
             sleep(Duration::from_millis(100)).await;
 
             writer
-                .write(&[
+                .write_all(&[
                     0x00, 0x03, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x11, 0x22, 0x22,
                 ])
                 .await
@@ -461,9 +623,499 @@ mod test {
                 .expect("Must write read fn.");
             assert_eq!(value, LabJackDataValue::Uint32(0x00112222));
 
-            transport.cancel.notify_one();
+            transport.close().await.expect("Must close");
+        });
+
+        let (reader, writer) = join!(join2, join);
+        reader.expect("Reader task must complete");
+        writer.expect("Writer task must complete");
+    }
+
+    #[tokio::test]
+    async fn validate_feedback() {
+        let (mut transport, mut writer) = setup().await;
+
+        let join = tokio::spawn(async move {
+            sleep(Duration::from_millis(100)).await;
+            writer
+                .write_all(&[
+                    0x00, 0x01, 0x00, 0x00, 0x00, 0x08, 0x01, 0x4C, 0x00, 0x11, 0x00, 0x11, 0x22,
+                    0x33,
+                ])
+                .await
+                .expect("Must write");
+        });
+
+        let join2 = tokio::spawn(async move {
+            let values = transport
+                .feedback(&[
+                    FeedbackFunction::ReadRegister(*TEST_UINT16),
+                    FeedbackFunction::ReadRegister(*TEST_UINT32),
+                ])
+                .await
+                .expect("Must perform feedback");
+
+            assert_eq!(
+                values,
+                vec![
+                    LabJackDataValue::Uint16(0x0011),
+                    LabJackDataValue::Uint32(0x00112233)
+                ]
+            );
+            transport.close().await.expect("Must close");
+        });
+
+        let (reader, writer) = join!(join2, join);
+        reader.expect("Reader task must complete");
+        writer.expect("Writer task must complete");
+    }
+
+    #[tokio::test]
+    async fn validate_read_block() {
+        let (mut transport, mut writer) = setup().await;
+
+        let join = tokio::spawn(async move {
+            sleep(Duration::from_millis(100)).await;
+            writer
+                .write_all(&[
+                    0x00, 0x01, 0x00, 0x00, 0x00, 0x0B, 0x01, 0x03, 0x08, 0x00, 0x11, 0x22, 0x33,
+                    0x00, 0x00, 0x00, 0x01,
+                ])
+                .await
+                .expect("Must write");
+        });
+
+        let join2 = tokio::spawn(async move {
+            let values = transport
+                .read_block(ReadBlockFunction::span(*TEST_UINT32, 2).expect("Must span"))
+                .await
+                .expect("Must read block");
+
+            assert_eq!(
+                values,
+                vec![
+                    LabJackDataValue::Uint32(0x00112233),
+                    LabJackDataValue::Uint32(0x00000001)
+                ]
+            );
+            transport.close().await.expect("Must close");
+        });
+
+        let (reader, writer) = join!(join2, join);
+        reader.expect("Reader task must complete");
+        writer.expect("Writer task must complete");
+    }
+
+    #[tokio::test]
+    async fn validate_read_string() {
+        let (mut transport, mut writer) = setup().await;
+
+        let join = tokio::spawn(async move {
+            let mut request = [0u8; 12];
+            writer.read_exact(&mut request).await.expect("Must read");
+            // Reads all 25 registers of the string.
+            assert_eq!(request[10..], [0x00, 0x19]);
+
+            let mut response = vec![0x00, 0x01, 0x00, 0x00, 0x00, 0x35, 0x01, 0x03, 0x32];
+            response.extend(b"Bench T7");
+            response.resize(9 + 50, 0x00);
+            writer.write_all(&response).await.expect("Must write");
+        });
+
+        let join2 = tokio::spawn(async move {
+            let value = transport
+                .read(ReadFunction(*DEVICE_NAME_DEFAULT))
+                .await
+                .expect("Must read string");
+
+            let expected = LabJackString::try_from("Bench T7").expect("Must fit");
+            assert_eq!(value, LabJackDataValue::String(expected));
+            transport.close().await.expect("Must close");
+        });
+
+        let (reader, writer) = join!(join2, join);
+        reader.expect("Reader task must complete");
+        writer.expect("Writer task must complete");
+    }
+
+    #[tokio::test]
+    async fn validate_timeout() {
+        let (mut transport, _writer) = setup().await;
+        transport.set_timeout(Duration::from_millis(100));
+
+        let value = transport.read(ReadFunction(*TEST_UINT32)).await;
+        assert!(matches!(value, Err(Error::Timeout)), "had {value:?}");
+        assert_eq!(transport.pending_requests(), 0);
+
+        transport.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn validate_unrouted_dropped() {
+        let (mut transport, mut writer) = setup().await;
+
+        let join = tokio::spawn(async move {
+            sleep(Duration::from_millis(100)).await;
+
+            // A response to a transaction which was never made, followed by the real one.
+            writer
+                .write_all(&[
+                    0x00, 0x09, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x01, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x11, 0x22, 0x33,
+                ])
+                .await
+                .expect("Must write");
+        });
+
+        let join2 = tokio::spawn(async move {
+            let value = transport
+                .read(ReadFunction(*TEST_UINT32))
+                .await
+                .expect("Must write read fn.");
+            assert_eq!(value, LabJackDataValue::Uint32(0x00112233));
+            assert_eq!(transport.unrouted_responses(), 1);
+            assert_eq!(transport.pending_requests(), 0);
+            transport.close().await.expect("Must close");
+        });
+
+        let (reader, writer) = join!(join2, join);
+        reader.expect("Reader task must complete");
+        writer.expect("Writer task must complete");
+    }
+
+    #[tokio::test]
+    async fn validate_pipelined_clones() {
+        let (mut transport, mut device) = setup().await;
+
+        // Accept both requests before answering either, then respond out of order,
+        // echoing each transaction id as the value read.
+        let join = tokio::spawn(async move {
+            let mut requests = [[0u8; 12]; 2];
+            for request in requests.iter_mut() {
+                device.read_exact(request).await.expect("Must read request");
+            }
+
+            for request in requests.iter().rev() {
+                let mut response = vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04];
+                response[0..2].copy_from_slice(&request[0..2]);
+                response.extend([0x00, 0x00, request[0], request[1]]);
+                device.write_all(&response).await.expect("Must write");
+            }
+        });
+
+        let mut first = transport.clone();
+        let mut second = transport.clone();
+
+        let (one, two) = join!(
+            first.read(ReadFunction(*TEST_UINT32)),
+            second.read(ReadFunction(*TEST_UINT32))
+        );
+
+        let mut values = vec![one.expect("Must read"), two.expect("Must read")];
+        values.sort_by_key(|value| value.as_f64() as u32);
+        assert_eq!(
+            values,
+            vec![LabJackDataValue::Uint32(1), LabJackDataValue::Uint32(2)]
+        );
+
+        join.await.expect("Device task must complete");
+        transport.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn validate_max_in_flight_shared() {
+        let (mut transport, _device) = setup().await;
+
+        // The clone is made before the limit is lowered, and must still observe it.
+        let mut clone = transport.clone();
+        clone.set_timeout(Duration::from_millis(200));
+        transport.set_max_in_flight(1).await;
+
+        let mut other = clone.clone();
+        let requests = tokio::spawn(async move {
+            join!(
+                clone.read(ReadFunction(*TEST_UINT32)),
+                other.read(ReadFunction(*TEST_UINT32))
+            )
         });
 
-        join!(join2, join);
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(transport.pending_requests(), 1);
+
+        let (one, two) = requests.await.expect("Request task must complete");
+        assert!(matches!(one, Err(Error::Timeout)), "had {one:?}");
+        assert!(matches!(two, Err(Error::Timeout)), "had {two:?}");
+
+        transport.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn validate_reconnect() {
+        env_logger::try_init().ok();
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Must bind to a port");
+        let addr = listener.local_addr().unwrap();
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let backoff = Backoff {
+            initial_delay: Duration::from_millis(250),
+            ..Backoff::default()
+        };
+        let mut transport = TcpTransport::with_backoff(stream, backoff);
+        let mut state = transport.state();
+
+        let (device, ..) = listener.accept().await.expect("Must accept connection");
+
+        // The device drops the connection whilst a request is in flight.
+        let mut reader = transport.clone();
+        let request = tokio::spawn(async move { reader.read(ReadFunction(*TEST_UINT32)).await });
+
+        sleep(Duration::from_millis(100)).await;
+        drop(device);
+
+        let failed = request.await.expect("Request task must complete");
+        assert!(matches!(failed, Err(Error::Disconnected)), "had {failed:?}");
+
+        state
+            .wait_for(|state| *state == ConnectionState::Reconnecting)
+            .await
+            .expect("Must observe reconnecting");
+
+        // The transport re-establishes the connection to the same peer.
+        let (mut device, ..) = listener.accept().await.expect("Must accept reconnection");
+        state
+            .wait_for(|state| *state == ConnectionState::Connected)
+            .await
+            .expect("Must observe connected");
+
+        let join = tokio::spawn(async move {
+            let mut request = [0u8; 12];
+            device
+                .read_exact(&mut request)
+                .await
+                .expect("Must read request");
+
+            let mut response = vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04];
+            response[0..2].copy_from_slice(&request[0..2]);
+            response.extend([0x00, 0x11, 0x22, 0x33]);
+            device.write_all(&response).await.expect("Must write");
+            device
+        });
+
+        let value = transport
+            .read(ReadFunction(*TEST_UINT32))
+            .await
+            .expect("Must read after reconnecting");
+        assert_eq!(value, LabJackDataValue::Uint32(0x00112233));
+
+        let _device = join.await.expect("Device task must complete");
+        transport.close().await.expect("Must close");
+        state
+            .wait_for(|state| *state == ConnectionState::Closed)
+            .await
+            .expect("Must observe closed");
+    }
+
+    #[tokio::test]
+    async fn validate_connect_backoff() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Must bind to a port");
+        let addr = listener.local_addr().expect("Must have an address");
+
+        let device = LabJackDevice {
+            port: addr.port(),
+            ..LabJackDevice::known(addr.ip(), DeviceType::T7, 470000000)
+        };
+        let options = ConnectOptions {
+            backoff: Backoff::never(),
+            ..ConnectOptions::default()
+        };
+
+        let (transport, accepted) = join!(
+            Tcp::connect_with_options(device, options),
+            listener.accept()
+        );
+        let transport = transport.expect("Must connect");
+        let mut state = transport.state();
+
+        // Without reconnection, losing the connection closes the transport.
+        drop(accepted.expect("Must accept connection"));
+        state
+            .wait_for(|state| *state == ConnectionState::Closed)
+            .await
+            .expect("Must observe closed");
+    }
+
+    #[tokio::test]
+    async fn validate_close_drains() {
+        let (transport, mut device) = setup().await;
+        let mut state = transport.state();
+
+        let join = tokio::spawn(async move {
+            sleep(Duration::from_millis(100)).await;
+            device
+                .write_all(&[
+                    0x00, 0x01, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x11, 0x22, 0x33,
+                ])
+                .await
+                .expect("Must write");
+            device
+        });
+
+        let mut reader = transport.clone();
+        let request = tokio::spawn(async move { reader.read(ReadFunction(*TEST_UINT32)).await });
+        sleep(Duration::from_millis(10)).await;
+
+        // Closing waits on the request in flight, rather than failing it.
+        let mut closer = transport.clone();
+        closer.close().await.expect("Must close");
+
+        let value = request.await.expect("Request task must complete");
+        assert_eq!(
+            value.expect("Must read"),
+            LabJackDataValue::Uint32(0x00112233)
+        );
+        assert_eq!(*state.borrow_and_update(), ConnectionState::Closed);
+
+        let mut after = transport.clone();
+        let refused = after.read(ReadFunction(*TEST_UINT32)).await;
+        assert!(matches!(refused, Err(Error::Closed)), "had {refused:?}");
+
+        join.await.expect("Device task must complete");
+    }
+
+    #[tokio::test]
+    async fn validate_drop_stops_listener() {
+        let (transport, _device) = setup().await;
+        let mut state = transport.state();
+
+        let clone = transport.clone();
+        drop(transport);
+        assert_eq!(*state.borrow(), ConnectionState::Connected);
+
+        drop(clone);
+        state
+            .wait_for(|state| *state == ConnectionState::Closed)
+            .await
+            .expect("Must observe closed");
+    }
+
+    #[tokio::test]
+    async fn validate_connect_options() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Must bind to a port");
+        let addr = listener.local_addr().expect("Must have an address");
+
+        // The device is reached on the port it was located at, rather than the default.
+        let device = LabJackDevice {
+            port: addr.port(),
+            ..LabJackDevice::known(addr.ip(), DeviceType::T7, 470000000)
+        };
+        let options = ConnectOptions {
+            nodelay: true,
+            keepalive: Some(Duration::from_secs(30)),
+            unit_id: 7,
+            ..ConnectOptions::default()
+        };
+
+        let (transport, accepted) = join!(
+            Tcp::connect_with_options(device, options),
+            listener.accept()
+        );
+        let mut transport = transport.expect("Must connect");
+        let (mut device, ..) = accepted.expect("Must accept connection");
+
+        let join = tokio::spawn(async move {
+            let mut request = [0u8; 12];
+            device.read_exact(&mut request).await.expect("Must read");
+            assert_eq!(request[6], 7, "Request must carry the unit id");
+
+            device
+                .write_all(&[
+                    request[0], request[1], 0x00, 0x00, 0x00, 0x07, 0x07, 0x03, 0x04, 0x00, 0x11,
+                    0x22, 0x33,
+                ])
+                .await
+                .expect("Must write");
+        });
+
+        let value = transport
+            .read(ReadFunction(*TEST_UINT32))
+            .await
+            .expect("Must read");
+        assert_eq!(value, LabJackDataValue::Uint32(0x00112233));
+
+        join.await.expect("Device task must complete");
+        transport.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn validate_exception_detail() {
+        let (mut transport, mut device) = setup().await;
+
+        let join = tokio::spawn(async move {
+            // Each failed read is followed by a read of the device's last error, which is
+            // attributed to the failing request by its transaction id. The first read is
+            // given its detail, while the second is given that of an unrelated request.
+            for detail_id in [None, Some([0xFF, 0xFF])] {
+                let mut request = [0u8; 12];
+                device.read_exact(&mut request).await.expect("Must read");
+                device
+                    .write_all(&[
+                        request[0], request[1], 0x00, 0x00, 0x00, 0x03, 0x01, 0x83, 0x03,
+                    ])
+                    .await
+                    .expect("Must write");
+
+                let [failed_high, failed_low] = detail_id.unwrap_or([request[0], request[1]]);
+
+                device.read_exact(&mut request).await.expect("Must read");
+                assert_eq!(&request[7..12], &[0x03, 0xD6, 0xD8, 0x00, 0x04]);
+                device
+                    .write_all(&[
+                        request[0],
+                        request[1],
+                        0x00,
+                        0x00,
+                        0x00,
+                        0x0B,
+                        0x01,
+                        0x03,
+                        0x08,
+                        0x09,
+                        0x42,
+                        0x00,
+                        0x00,
+                        0x00,
+                        0x00,
+                        failed_high,
+                        failed_low,
+                    ])
+                    .await
+                    .expect("Must write");
+            }
+        });
+
+        let value = transport.read(ReadFunction(*TEST_UINT32)).await;
+        assert!(
+            matches!(value, Err(Error::Device(LabJackErrorCode::AinRangeInvalid))),
+            "had {value:?}"
+        );
+
+        let value = transport.read(ReadFunction(*TEST_UINT32)).await;
+        assert!(
+            matches!(
+                value,
+                Err(Error::Exception(ExceptionCode::IllegalDataValue))
+            ),
+            "had {value:?}"
+        );
+
+        join.await.expect("Device task must complete");
+        transport.close().await.expect("Must close");
     }
 }
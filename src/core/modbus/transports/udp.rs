@@ -0,0 +1,451 @@
+use crate::core::modbus::exchange::{self, Exchange, Pipeline};
+use crate::prelude::*;
+use crate::queue::listener::ListenerHandle;
+use crate::queue::router::Router;
+use log::{debug, trace, warn};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::sync::Notify;
+
+/// The default duration to wait for a response to each transmission of a request,
+/// before it is sent again. Datagrams are lost silently, so this is kept short
+/// relative to the [`DEFAULT_SEND_RECEIVE_TIMEOUT`] of a TCP connection.
+pub const DEFAULT_UDP_ATTEMPT_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The default number of times a request is sent again after going unanswered.
+pub const DEFAULT_UDP_RETRIES: usize = 2;
+
+/// A Modbus connection to a LabJack device over UDP.
+///
+/// There is no session to establish, which suits links on which setting up a TCP
+/// connection is expensive. In exchange, a datagram may be lost, and so a request
+/// which goes unanswered within the [`timeout`](UdpTransport::timeout) is sent again,
+/// under the same transaction id, up to [`retries`](UdpTransport::retries) times.
+/// Should an earlier transmission be answered late, that response is accepted, and any
+/// duplicate which follows is dropped.
+///
+/// Like [`TcpTransport`], the transport is a cheap handle which may be cloned and shared
+/// between tasks, with requests from each handle pipelined over the same socket.
+#[derive(Debug, Clone)]
+pub struct UdpTransport {
+    pipeline: Pipeline,
+
+    socket: Arc<UdpSocket>,
+    listener: Arc<ListenerHandle>,
+
+    timeout: Duration,
+    retries: usize,
+}
+
+/// Receives each datagram from the device, routing responses to their requests.
+struct Listener {
+    router: Arc<Router>,
+    cancel: Arc<Notify>,
+    socket: Arc<UdpSocket>,
+}
+
+impl UdpTransport {
+    /// Creates the transport over a socket which has been
+    /// [connected](UdpSocket::connect) to the device.
+    pub fn new(socket: UdpSocket) -> UdpTransport {
+        UdpTransport::with_unit_id(socket, BASE_UNIT_ID)
+    }
+
+    /// Creates the transport over a connected socket, addressing requests to `unit_id`.
+    fn with_unit_id(socket: UdpSocket, unit_id: u8) -> UdpTransport {
+        let socket = Arc::new(socket);
+        let pipeline = Pipeline::new(unit_id);
+        let notify = Arc::new(Notify::new());
+
+        let listener = Listener {
+            router: pipeline.router(),
+            cancel: Arc::clone(&notify),
+            socket: Arc::clone(&socket),
+        };
+
+        let task = tokio::spawn(listener.run());
+
+        UdpTransport {
+            pipeline,
+
+            socket,
+            listener: Arc::new(ListenerHandle::new(notify, task)),
+
+            timeout: DEFAULT_UDP_ATTEMPT_TIMEOUT,
+            retries: DEFAULT_UDP_RETRIES,
+        }
+    }
+
+    /// Sets the number of requests which may be awaiting a response at once. Further
+    /// requests wait for an earlier one to complete before being sent.
+    ///
+    /// The limit is shared by every clone of this handle. Lowering it waits for
+    /// enough of the requests in flight to complete.
+    pub async fn set_max_in_flight(&self, max_in_flight: usize) {
+        self.pipeline.set_max_in_flight(max_in_flight).await;
+    }
+
+    /// The number of requests awaiting a response.
+    pub fn pending_requests(&self) -> usize {
+        self.pipeline.pending()
+    }
+
+    /// The number of responses received which matched no pending request, and so
+    /// were dropped. For example, the answer to a retransmission which came too late.
+    pub fn unrouted_responses(&self) -> u64 {
+        self.pipeline.unrouted()
+    }
+
+    /// The duration to wait for a response to each transmission of a request.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets the duration to wait for a response to each transmission of a request.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// The number of times an unanswered request is sent again.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Sets the number of times an unanswered request is sent again, after which
+    /// it fails with [`Error::Timeout`]. With no retries, a request is sent once.
+    pub fn set_retries(&mut self, retries: usize) {
+        self.retries = retries;
+    }
+}
+
+impl Exchange for UdpTransport {
+    fn compose<F>(&self, compose: F) -> Result<ComposedMessage, Error>
+    where
+        F: FnOnce(&mut Compositor) -> Result<ComposedMessage, Error>,
+    {
+        self.pipeline.compose(compose)
+    }
+
+    /// Sends a composed message and awaits the response with the same transaction id,
+    /// sending it again each time the timeout elapses until the retries are exhausted.
+    ///
+    /// The response slot is held across every transmission, so a response to any
+    /// of them completes the request.
+    async fn exchange(&self, content: &[u8], header: &Header) -> Result<(Header, Vec<u8>), Error> {
+        let transaction_id = header.transaction_id;
+
+        let (_permit, mut slot) = self.pipeline.reserve(transaction_id).await?;
+
+        for attempt in 0..=self.retries {
+            if attempt > 0 {
+                debug!("Retransmitting request, attempt {attempt}. TxnID={transaction_id}");
+            }
+
+            self.socket.send(content).await.map_err(Error::Io)?;
+
+            match tokio::time::timeout(self.timeout, slot.response()).await {
+                Ok(response) => {
+                    return response.map_err(|err| match err {
+                        Error::Queue(QueueError::SlotClosed) => Error::Closed,
+                        err => err,
+                    })
+                }
+                Err(_) => trace!(
+                    "No response after {:?}. TxnID={transaction_id}",
+                    self.timeout
+                ),
+            }
+        }
+
+        warn!(
+            "Request timed out after {} attempts. TxnID={transaction_id}",
+            self.retries + 1
+        );
+        Err(Error::Timeout)
+    }
+}
+
+impl Transport for UdpTransport {
+    type Error = Error;
+
+    /// Waits for every request in flight to complete, then stops the listener.
+    /// Requests made afterwards, from this handle or its clones, fail with [`Error::Closed`].
+    async fn close(&mut self) -> Result<(), Self::Error> {
+        self.pipeline.close().await;
+        self.listener.stop().await;
+
+        debug!("Transport closed.");
+        Ok(())
+    }
+
+    async fn write(&mut self, function: WriteFunction) -> Result<(), Self::Error> {
+        exchange::write(self, function).await
+    }
+
+    async fn read(&mut self, function: ReadFunction) -> Result<LabJackDataValue, Self::Error> {
+        exchange::read(self, function).await
+    }
+
+    async fn read_block(
+        &mut self,
+        function: ReadBlockFunction,
+    ) -> Result<Vec<LabJackDataValue>, Self::Error> {
+        exchange::read_block(self, function).await
+    }
+
+    async fn write_block(&mut self, function: WriteBlockFunction) -> Result<(), Self::Error> {
+        exchange::write_block(self, function).await
+    }
+
+    async fn feedback(
+        &mut self,
+        functions: &[FeedbackFunction],
+    ) -> Result<Vec<LabJackDataValue>, Self::Error> {
+        exchange::feedback(self, functions).await
+    }
+}
+
+impl Listener {
+    async fn run(self) {
+        let mut buffer = vec![0u8; MAX_DATA_LENGTH];
+
+        loop {
+            tokio::select! {
+                received = self.socket.recv(&mut buffer) => {
+                    match received {
+                        Ok(size) => self.route(buffer.get(..size).unwrap_or_default()),
+                        // Errors such as an unreachable port are reported against the next
+                        // receive, but the socket remains usable; the request is retried.
+                        Err(err) => warn!("Error receiving from UDP socket: {err}"),
+                    }
+                }
+                _ = self.cancel.notified() => {
+                    break
+                }
+            }
+        }
+
+        self.router.close_all();
+        debug!("Listening ended, socket closed.")
+    }
+
+    /// Routes a single datagram, which must hold exactly one Modbus frame.
+    fn route(&self, datagram: &[u8]) {
+        let header = match datagram.get(..MODBUS_HEADER_SIZE).map(Header::unpack) {
+            Some(Ok(header)) => header,
+            _ => {
+                warn!(
+                    "Dropped datagram without a valid header. Size={}",
+                    datagram.len()
+                );
+                return;
+            }
+        };
+
+        // The length counts the unit id, which is already a part of the header.
+        if datagram.len() + 1 != MODBUS_HEADER_SIZE + header.length as usize {
+            warn!(
+                "Dropped datagram of unexpected size {}. TxnID={}",
+                datagram.len(),
+                header.transaction_id
+            );
+            return;
+        }
+
+        trace!(
+            "Obtained datagram of size {}. TxnID={}",
+            header.length,
+            header.transaction_id
+        );
+        self.router.publish(header, datagram.to_vec());
+    }
+}
+
+/// The UDP ModBus client.
+///
+/// Example:
+/// ```no_run
+/// // Import prelude items
+/// use labjack::prelude::*;
+///
+/// # async fn docs() {
+/// // Connect to a LabJack at a known address over UDP
+/// let known_ip = "192.168.1.25".parse().expect("Must resolve");
+/// let known_device = LabJackDevice::known(known_ip, DeviceType::TSERIES, 470000000);
+/// let mut device = LabJack::connect_with::<Udp>(known_device).await.expect("Must connect");
+///
+/// let voltage = device.read_register(*AIN55).await.expect("Must read");
+/// println!("Voltage(as f64)={}", voltage.as_f64());
+/// # }
+/// ```
+pub struct Udp;
+
+impl Connect for Udp {
+    type Transport = UdpTransport;
+
+    async fn connect(device: LabJackDevice) -> Result<Self::Transport, Error> {
+        Udp::connect_with_options(device, ConnectOptions::default()).await
+    }
+
+    async fn connect_with_options(
+        device: LabJackDevice,
+        options: ConnectOptions,
+    ) -> Result<Self::Transport, Error> {
+        let local = options.local_address.unwrap_or(match device.ip_address {
+            IpAddr::V4(_) => SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0),
+            IpAddr::V6(_) => SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0),
+        });
+
+        let socket = UdpSocket::bind(local).await.map_err(Error::Io)?;
+        socket
+            .connect(options.address(&device))
+            .await
+            .map_err(Error::Io)?;
+
+        Ok(UdpTransport::with_unit_id(socket, options.unit_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use tokio::net::UdpSocket;
+
+    use crate::core::{Error, LabJackDataValue, ReadFunction};
+    use crate::prelude::{Transport, UdpTransport, TEST_UINT32};
+
+    async fn setup() -> (UdpTransport, UdpSocket) {
+        env_logger::try_init().ok();
+
+        let device = UdpSocket::bind("127.0.0.1:0")
+            .await
+            .expect("Must bind to a port");
+        let socket = UdpSocket::bind("127.0.0.1:0")
+            .await
+            .expect("Must bind to a port");
+
+        socket
+            .connect(device.local_addr().expect("Must have an address"))
+            .await
+            .expect("Must connect");
+        device
+            .connect(socket.local_addr().expect("Must have an address"))
+            .await
+            .expect("Must connect");
+
+        (UdpTransport::new(socket), device)
+    }
+
+    /// Receives a request, returning its transaction id.
+    async fn receive(device: &UdpSocket) -> [u8; 2] {
+        let mut buffer = [0u8; 64];
+        let size = device.recv(&mut buffer).await.expect("Must receive");
+        assert_eq!(size, 12, "Read request must be 12 bytes");
+
+        [buffer[0], buffer[1]]
+    }
+
+    async fn reply_uint32(device: &UdpSocket, [high, low]: [u8; 2]) {
+        device
+            .send(&[
+                high, low, 0x00, 0x00, 0x00, 0x07, 0x01, 0x03, 0x04, 0x00, 0x11, 0x22, 0x33,
+            ])
+            .await
+            .expect("Must send");
+    }
+
+    #[tokio::test]
+    async fn validate_read() {
+        let (mut transport, device) = setup().await;
+
+        let join = tokio::spawn(async move {
+            let transaction_id = receive(&device).await;
+            reply_uint32(&device, transaction_id).await;
+        });
+
+        let value = transport
+            .read(ReadFunction(*TEST_UINT32))
+            .await
+            .expect("Must read");
+        assert_eq!(value, LabJackDataValue::Uint32(0x00112233));
+        assert_eq!(transport.pending_requests(), 0);
+
+        join.await.expect("Device task must complete");
+        transport.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn validate_retransmit() {
+        let (mut transport, device) = setup().await;
+        transport.set_timeout(Duration::from_millis(100));
+
+        let join = tokio::spawn(async move {
+            // The first request is lost, so only the retransmission is answered.
+            let first = receive(&device).await;
+            let second = receive(&device).await;
+            assert_eq!(
+                first, second,
+                "Must retransmit under the same transaction id"
+            );
+
+            reply_uint32(&device, second).await;
+        });
+
+        let value = transport
+            .read(ReadFunction(*TEST_UINT32))
+            .await
+            .expect("Must read");
+        assert_eq!(value, LabJackDataValue::Uint32(0x00112233));
+
+        join.await.expect("Device task must complete");
+        transport.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn validate_late_response_dropped() {
+        let (mut transport, device) = setup().await;
+        transport.set_timeout(Duration::from_millis(100));
+
+        let join = tokio::spawn(async move {
+            // Both transmissions are answered, the first too late to prevent a retransmission.
+            let first = receive(&device).await;
+            let second = receive(&device).await;
+
+            reply_uint32(&device, first).await;
+            reply_uint32(&device, second).await;
+        });
+
+        let value = transport
+            .read(ReadFunction(*TEST_UINT32))
+            .await
+            .expect("Must read");
+        assert_eq!(value, LabJackDataValue::Uint32(0x00112233));
+
+        join.await.expect("Device task must complete");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(transport.unrouted_responses(), 1);
+
+        transport.close().await.expect("Must close");
+    }
+
+    #[tokio::test]
+    async fn validate_retries_exhausted() {
+        let (mut transport, device) = setup().await;
+        transport.set_timeout(Duration::from_millis(50));
+        transport.set_retries(1);
+
+        let value = transport.read(ReadFunction(*TEST_UINT32)).await;
+        assert!(matches!(value, Err(Error::Timeout)), "had {value:?}");
+        assert_eq!(transport.pending_requests(), 0);
+
+        // Sent once, then retried once.
+        receive(&device).await;
+        receive(&device).await;
+
+        transport.close().await.expect("Must close");
+        let value = transport.read(ReadFunction(*TEST_UINT32)).await;
+        assert!(matches!(value, Err(Error::Closed)), "had {value:?}");
+    }
+}
@@ -0,0 +1,87 @@
+// The expansion of ranged register names, such as `AIN#(0:254)`, shared by the build
+// script and the runtime `RegisterMap`, such that both name every register alike.
+//
+// This file is `include!`d by `build.rs`, and so may not depend upon the rest of the crate.
+
+/// A register name standing for a range of registers, such as `AIN#(0:149)_EF_READ_C`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangedName<'a> {
+    /// The name preceding the range, such as `AIN`.
+    pub base: &'a str,
+    pub first: u16,
+    pub last: u16,
+    /// The name following the range, without its leading underscore, such as `EF_READ_C`.
+    pub suffix: &'a str,
+}
+
+impl<'a> RangedName<'a> {
+    /// Parses a ranged name. Yields `None` for a name without a range, or with a malformed one.
+    pub fn parse(name: &'a str) -> Option<RangedName<'a>> {
+        let (base, rest) = name.split_once("#(")?;
+        let (range, suffix) = rest.split_once(')')?;
+        let (first, last) = range.split_once(':')?;
+
+        Some(RangedName {
+            base,
+            first: first.parse().ok()?,
+            last: last.parse().ok()?,
+            suffix: suffix.trim_start_matches('_'),
+        })
+    }
+
+    /// The name of the register at index `i` of the range, such as `AIN3_EF_READ_C`.
+    pub fn name(&self, i: u16) -> String {
+        match self.suffix {
+            "" => format!("{}{i}", self.base),
+            suffix => format!("{}{i}_{suffix}", self.base),
+        }
+    }
+}
+
+/// Expands a register name into each name it stands for, alongside its index within the
+/// range. A name without a range stands for itself alone, with no index. Yields `None`
+/// for a name with a malformed range.
+pub fn expand_register_name(name: &str) -> Option<Vec<(String, Option<u16>)>> {
+    if !name.contains("#(") {
+        return Some(vec![(name.to_string(), None)]);
+    }
+
+    let ranged = RangedName::parse(name)?;
+    Some(
+        (ranged.first..=ranged.last)
+            .map(|i| (ranged.name(i), Some(i)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expands_ranges() {
+        assert_eq!(
+            expand_register_name("AIN#(0:2)"),
+            Some(vec![
+                ("AIN0".to_string(), Some(0)),
+                ("AIN1".to_string(), Some(1)),
+                ("AIN2".to_string(), Some(2)),
+            ])
+        );
+        assert_eq!(
+            expand_register_name("AIN#(148:149)_EF_READ_C"),
+            Some(vec![
+                ("AIN148_EF_READ_C".to_string(), Some(148)),
+                ("AIN149_EF_READ_C".to_string(), Some(149)),
+            ])
+        );
+        assert_eq!(
+            expand_register_name("PRODUCT_ID"),
+            Some(vec![("PRODUCT_ID".to_string(), None)])
+        );
+
+        assert_eq!(expand_register_name("AIN#(0)"), None);
+        assert_eq!(expand_register_name("AIN#(0:x)"), None);
+        assert_eq!(expand_register_name("AIN#(0:1"), None);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// A value read from a device, alongside where and when it came from, such that it may
+/// be persisted by a historian. Produced by [`LabJackClient::read_reading`] and the like.
+///
+/// The converted `value` is an `f64` of the raw value unless read through a conversion,
+/// as with [`LabJackClient::read_reading_with`].
+///
+/// ```
+/// use labjack::prelude::*;
+///
+/// # async fn docs() {
+/// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+///     .await
+///     .expect("Must connect");
+///
+/// let reading = device.read_reading(*AIN0).await.expect("Must read");
+/// assert_eq!(reading.register, RegisterList::Ain0);
+/// println!("{} read {} after {:?}", reading.serial_number.0, reading.value, reading.latency);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reading<V = f64> {
+    pub serial_number: LabJackSerialNumber,
+    pub register: RegisterList,
+    /// The value as decoded from the device.
+    pub raw: LabJackDataValue,
+    /// The value after conversion.
+    pub value: V,
+    /// The host time at which the request was made.
+    pub timestamp: SystemTime,
+    /// The time taken from making the request to receiving its response. Readings
+    /// taken by the same request share its latency.
+    pub latency: Duration,
+}
+
+impl<V> Reading<V> {
+    /// Converts the value of the reading, keeping its provenance.
+    pub fn map<U>(self, convert: impl FnOnce(V) -> U) -> Reading<U> {
+        Reading {
+            serial_number: self.serial_number,
+            register: self.register,
+            raw: self.raw,
+            value: convert(self.value),
+            timestamp: self.timestamp,
+            latency: self.latency,
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! A register map loaded at runtime from a LabJack constants file.
+//!
+//! The registers of this crate are generated from the constants file it was built with.
+//! Tools which must track the newest constants, without recompiling, may instead load a
+//! [`RegisterMap`] from any file of the same schema.
+
+use crate::core::data_types::check_compatibility;
+use crate::core::ranged_name::expand_register_name;
+use crate::prelude::*;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A register described by a constants file loaded at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappedRegister {
+    /// The name of the register, such as `AIN0`, with any range expanded.
+    pub name: String,
+    pub address: Address,
+    pub data_type: LabJackDataType,
+    pub access: AccessControl,
+    pub default_value: Option<f64>,
+    pub description: Option<String>,
+    /// The devices which support the register. Devices unknown to [`DeviceType`] are omitted.
+    pub compatibility: Vec<DeviceCompatibility>,
+    /// Whether the register is listed as beta, and so subject to change.
+    pub beta: bool,
+}
+
+impl MappedRegister {
+    /// Checks the register may be used on the `device`, running the given `firmware`
+    /// version, if known, as with [`Register::check_compatibility`].
+    pub fn check_compatibility(
+        &self,
+        device: DeviceType,
+        firmware: Option<f32>,
+    ) -> Result<(), Error> {
+        check_compatibility(self.name.clone(), &self.compatibility, device, firmware)
+    }
+
+    /// Checks the register permits the given `access`, being one of
+    /// [`AccessControl::ReadableCtrl`] or [`AccessControl::WritableCtrl`].
+    pub fn check_access(&self, access: AccessControl) -> Result<(), Error> {
+        if self.access == AccessControl::AllCtrl || self.access == access {
+            return Ok(());
+        }
+
+        Err(Error::Incompatible(Incompatibility::Access {
+            register: self.name.clone().into(),
+            access: self.access,
+        }))
+    }
+
+    /// The register of the same name compiled into this crate, provided its address
+    /// and type are unchanged. Registers introduced by the loaded constants have none.
+    pub fn register(&self) -> Option<Register> {
+        Register::by_name(&self.name)
+            .filter(|register| register.address == self.address)
+            .filter(|register| register.data_type == self.data_type)
+    }
+}
+
+/// The registers of a LabJack constants file, indexed by name and address.
+///
+/// Where several registers share a name, alternative name or address, the first listed
+/// in the file is found, with the beta registers listed after every other. The registers
+/// may be read and written through [`LabJackClient::read_mapped`] and the like, whether or
+/// not this crate was built with them.
+///
+/// ```
+/// use labjack::prelude::*;
+///
+/// let json = r#"{
+///     "header": { "version": "2099.01.01.A" },
+///     "registers": [
+///         { "address": 0, "name": "AIN#(0:1)", "type": "FLOAT32", "devices": ["T7"], "readwrite": "R" },
+///         { "address": 61990, "name": "NEW_FEATURE", "type": "UINT32", "devices": [{ "device": "T7", "fwmin": 2.0 }], "readwrite": "RW" }
+///     ]
+/// }"#;
+///
+/// let map = RegisterMap::from_json(json).expect("Must parse");
+/// assert_eq!(map.version(), "2099.01.01.A");
+///
+/// let ain1 = map.by_name("ain1").expect("Must exist");
+/// assert_eq!(ain1.address, 2);
+/// assert_eq!(ain1.register().map(|r| r.name), Some(RegisterList::Ain1));
+///
+/// let new = map.by_address(61990).expect("Must exist");
+/// assert_eq!(new.name, "NEW_FEATURE");
+/// assert!(new.register().is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RegisterMap {
+    version: String,
+    registers: Vec<MappedRegister>,
+    names: HashMap<String, usize>,
+    addresses: HashMap<Address, usize>,
+}
+
+#[derive(Deserialize)]
+struct ConstantsFile {
+    header: ConstantsHeader,
+    registers: Vec<ConstantsRegister>,
+    #[serde(default)]
+    registers_beta: Vec<ConstantsRegister>,
+}
+
+#[derive(Deserialize)]
+struct ConstantsHeader {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct ConstantsRegister {
+    name: String,
+    address: Address,
+    r#type: String,
+    readwrite: String,
+    #[serde(default)]
+    devices: Vec<ConstantsDevice>,
+    #[serde(default)]
+    altnames: Vec<String>,
+    default: Option<f64>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConstantsDevice {
+    Name(String),
+    Detailed { device: String, fwmin: Option<f32> },
+}
+
+impl RegisterMap {
+    /// Parses the contents of a constants file, such as the `ljm_constants.json`
+    /// distributed by LabJack.
+    pub fn from_json(json: &str) -> Result<RegisterMap, Error> {
+        let file: ConstantsFile = serde_json::from_str(json)
+            .map_err(|err| Error::InvalidData(Reason::Custom(err.to_string())))?;
+
+        let mut map = RegisterMap {
+            version: file.header.version,
+            ..RegisterMap::default()
+        };
+
+        let tables = [(file.registers, false), (file.registers_beta, true)];
+        for (registers, beta) in tables {
+            for register in registers {
+                map.insert(register, beta)?;
+            }
+        }
+
+        Ok(map)
+    }
+
+    fn insert(&mut self, register: ConstantsRegister, beta: bool) -> Result<(), Error> {
+        let invalid = |field: &str| {
+            Error::InvalidData(Reason::Custom(format!(
+                "Register {} has an invalid {field}",
+                register.name
+            )))
+        };
+
+        let data_type = match register.r#type.as_str() {
+            "UINT16" => LabJackDataType::Uint16,
+            "UINT32" => LabJackDataType::Uint32,
+            "UINT64" => LabJackDataType::Uint64,
+            "INT32" => LabJackDataType::Int32,
+            "FLOAT32" => LabJackDataType::Float32,
+            "STRING" => LabJackDataType::String,
+            "BYTE" => LabJackDataType::Byte,
+            _ => return Err(invalid("type")),
+        };
+
+        let access = match register.readwrite.as_str() {
+            "R" => AccessControl::ReadableCtrl,
+            "W" => AccessControl::WritableCtrl,
+            "RW" => AccessControl::AllCtrl,
+            _ => return Err(invalid("readwrite")),
+        };
+
+        let compatibility = register
+            .devices
+            .iter()
+            .filter_map(|device| {
+                let (name, min_firmware) = match device {
+                    ConstantsDevice::Name(name) => (name, None),
+                    ConstantsDevice::Detailed { device, fwmin } => (device, *fwmin),
+                };
+
+                let device = match name.as_str() {
+                    "T4" => DeviceType::T4,
+                    "T7" => DeviceType::T7,
+                    "T8" => DeviceType::T8,
+                    "DIGIT" => DeviceType::DIGIT,
+                    _ => return None,
+                };
+
+                Some(DeviceCompatibility {
+                    device,
+                    min_firmware,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let names = expand_register_name(&register.name).ok_or_else(|| invalid("name"))?;
+        for (name, offset) in &names {
+            let address = offset
+                .unwrap_or(0)
+                .checked_mul(data_type.size())
+                .and_then(|offset| register.address.checked_add(offset))
+                .ok_or_else(|| invalid("address"))?;
+
+            let index = self.registers.len();
+            self.names.entry(name.to_ascii_uppercase()).or_insert(index);
+            self.addresses.entry(address).or_insert(index);
+
+            self.registers.push(MappedRegister {
+                name: name.clone(),
+                address,
+                data_type,
+                access,
+                default_value: register.default,
+                description: register.description.clone(),
+                compatibility: compatibility.clone(),
+                beta,
+            });
+        }
+
+        // Alternative names are ranged in step with the name they alias.
+        for altname in &register.altnames {
+            let aliases = expand_register_name(altname).ok_or_else(|| invalid("altname"))?;
+            for ((alias, _), (name, _)) in aliases.iter().zip(&names) {
+                if let Some(index) = self.names.get(&name.to_ascii_uppercase()).copied() {
+                    self.names
+                        .entry(alias.to_ascii_uppercase())
+                        .or_insert(index);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The version of the constants file, such as `2024.12.16.A`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Finds a register by its name, or an alternative name, ignoring case. Where several
+    /// share the name, the first listed in the file is found.
+    pub fn by_name(&self, name: &str) -> Option<&MappedRegister> {
+        let index = self.names.get(&name.to_ascii_uppercase())?;
+        self.registers.get(*index)
+    }
+
+    /// Finds the register at the given address. Where several share the address,
+    /// the first listed in the file is found.
+    pub fn by_address(&self, address: Address) -> Option<&MappedRegister> {
+        let index = self.addresses.get(&address)?;
+        self.registers.get(*index)
+    }
+
+    /// Every register of the map, in the order of the file.
+    pub fn iter(&self) -> impl Iterator<Item = &MappedRegister> {
+        self.registers.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.registers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BUNDLED: &str = include_str!("../../resources/ljm_constants.json");
+
+    #[test]
+    fn bundled_matches_generated() {
+        let map = RegisterMap::from_json(BUNDLED).expect("Must parse");
+        assert_eq!(map.version(), CONSTANTS_VERSION);
+
+        for register in [*AIN0, *AIN254, *DIO22_EF_CONFIG_A, *DEVICE_NAME_DEFAULT] {
+            let mapped = map.by_name(register.name.name()).expect("Must exist");
+            assert_eq!(mapped.register().map(|r| r.name), Some(register.name));
+        }
+
+        let power = map.by_name("power_mode").expect("Must exist");
+        assert_eq!(power.compatibility, POWER_MODE.name.compatibility());
+        assert_eq!(power.access, AccessControl::AllCtrl);
+
+        assert_eq!(
+            map.by_address(AIN3.address).map(|r| r.name.as_str()),
+            Some("AIN3")
+        );
+    }
+
+    #[test]
+    fn first_duplicate_found() {
+        let json = r#"{
+            "header": { "version": "1" },
+            "registers": [
+                { "address": 10, "name": "FIRST", "type": "UINT16", "readwrite": "R" },
+                { "address": 10, "name": "SECOND", "type": "UINT16", "readwrite": "R" },
+                { "address": 20, "name": "FIRST", "type": "UINT32", "readwrite": "R" }
+            ]
+        }"#;
+
+        let map = RegisterMap::from_json(json).expect("Must parse");
+        assert_eq!(map.by_name("FIRST").map(|r| r.address), Some(10));
+        assert_eq!(map.by_address(10).map(|r| r.name.as_str()), Some("FIRST"));
+        assert_eq!(map.by_address(20).map(|r| r.name.as_str()), Some("FIRST"));
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert!(RegisterMap::from_json("{}").is_err());
+
+        let unknown_type = r#"{
+            "header": { "version": "1" },
+            "registers": [{ "address": 0, "name": "X", "type": "DOUBLE", "readwrite": "R" }]
+        }"#;
+        assert!(matches!(
+            RegisterMap::from_json(unknown_type),
+            Err(Error::InvalidData(Reason::Custom(..)))
+        ));
+    }
+}
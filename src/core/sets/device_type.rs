@@ -1,10 +1,12 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum DeviceType {
     T4,
     T7,
     T8,
+    DIGIT,
     TSERIES,
     ANY,
     EMULATED(i32),
@@ -17,6 +19,7 @@ impl From<i32> for DeviceType {
             4 => DeviceType::T4,
             7 => DeviceType::T7,
             8 => DeviceType::T8,
+            200 => DeviceType::DIGIT,
             -999..=-1 => DeviceType::EMULATED(value),
             value => DeviceType::UNKNOWN(value),
         }
@@ -29,6 +32,7 @@ impl Display for DeviceType {
             DeviceType::T4 => "T4".to_string(),
             DeviceType::T7 => "T7".to_string(),
             DeviceType::T8 => "T8".to_string(),
+            DeviceType::DIGIT => "DIGIT".to_string(),
             DeviceType::TSERIES => "TSERIES".to_string(),
             DeviceType::ANY => "ANY".to_string(),
 
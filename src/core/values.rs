@@ -0,0 +1,65 @@
+use crate::prelude::*;
+
+/// A type naming the values a register accepts, such as [`StreamOutEnable`].
+///
+/// An implementation is generated for each register listing named values in the LabJack
+/// constants file. Values without a name are preserved, rather than rejected, so that
+/// reading a register which holds one does not fail.
+///
+/// ```
+/// use labjack::prelude::*;
+///
+/// # async fn docs() {
+/// let mut device = LabJack::connect::<Emulated>(LabJackSerialNumber::emulated())
+///     .await
+///     .expect("Must connect");
+///
+/// device
+///     .write_value(*STREAM_OUT0_ENABLE, StreamOutEnable::Enabled)
+///     .await
+///     .expect("Must write");
+///
+/// let enabled: StreamOutEnable = device.read_value(*STREAM_OUT0_ENABLE).await.expect("Must read");
+/// assert_eq!(enabled, StreamOutEnable::Enabled);
+/// # }
+/// ```
+pub trait RegisterValue: Copy {
+    /// The registers which accept these values.
+    const REGISTERS: &'static [RegisterList];
+
+    /// The named value for `value`, or [`Unknown`](StreamOutEnable::Unknown) if it has no name.
+    fn from_value(value: u32) -> Self;
+
+    /// The raw value written to the register.
+    fn value(&self) -> u32;
+
+    /// Encodes the value as the data type of the `register`, provided
+    /// it is one of the [`RegisterValue::REGISTERS`].
+    fn encode(&self, register: Register) -> Result<LabJackDataValue, Error> {
+        if !Self::REGISTERS.contains(&register.name) {
+            return Err(Error::InvalidData(Reason::RegisterMismatch));
+        }
+
+        match register.data_type {
+            LabJackDataType::Uint16 => u16::try_from(self.value())
+                .map(LabJackDataValue::Uint16)
+                .map_err(|_| Error::InvalidData(Reason::EncodingError)),
+            LabJackDataType::Uint32 => Ok(LabJackDataValue::Uint32(self.value())),
+            _ => Err(Error::InvalidData(Reason::RegisterMismatch)),
+        }
+    }
+
+    /// Decodes a value read from the `register`, provided it is
+    /// one of the [`RegisterValue::REGISTERS`].
+    fn decode(register: Register, value: LabJackDataValue) -> Result<Self, Error> {
+        if !Self::REGISTERS.contains(&register.name) {
+            return Err(Error::InvalidData(Reason::RegisterMismatch));
+        }
+
+        match value {
+            LabJackDataValue::Uint16(value) => Ok(Self::from_value(u32::from(value))),
+            LabJackDataValue::Uint32(value) => Ok(Self::from_value(value)),
+            _ => Err(Error::InvalidData(Reason::DecodingError)),
+        }
+    }
+}
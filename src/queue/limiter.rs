@@ -0,0 +1,60 @@
+use crate::prelude::Error;
+use tokio::sync::{Mutex, Semaphore, SemaphorePermit};
+
+/// Bounds the number of requests awaiting a response at once.
+///
+/// A single limiter is shared by every clone of a transport, such that changing the
+/// limit from any handle applies to all of them, and closing waits on requests
+/// in flight from each.
+#[derive(Debug)]
+pub(crate) struct Limiter {
+    permits: Semaphore,
+    /// The number of permits the semaphore holds in total. Locked whilst resizing or
+    /// closing, so that the two never act upon a stale count.
+    limit: Mutex<usize>,
+}
+
+impl Limiter {
+    pub(crate) fn new(limit: usize) -> Limiter {
+        let limit = limit.clamp(1, Semaphore::MAX_PERMITS);
+
+        Limiter {
+            permits: Semaphore::new(limit),
+            limit: Mutex::new(limit),
+        }
+    }
+
+    /// Waits for a request to be permitted. Fails once the limiter is closed.
+    pub(crate) async fn acquire(&self) -> Result<SemaphorePermit<'_>, Error> {
+        self.permits.acquire().await.map_err(|_| Error::Closed)
+    }
+
+    /// Changes the limit. Lowering it waits for enough requests in flight to complete.
+    pub(crate) async fn resize(&self, limit: usize) {
+        let limit = limit.clamp(1, Semaphore::MAX_PERMITS);
+        let mut current = self.limit.lock().await;
+
+        if limit > *current {
+            self.permits.add_permits(limit - *current);
+        } else if limit < *current {
+            let excess = u32::try_from(*current - limit).unwrap_or(u32::MAX);
+            match self.permits.acquire_many(excess).await {
+                Ok(permits) => permits.forget(),
+                Err(_) => return,
+            }
+        }
+
+        *current = limit;
+    }
+
+    /// Waits for every request in flight to complete, then refuses any further requests.
+    pub(crate) async fn close(&self) {
+        let limit = self.limit.lock().await;
+
+        let all = u32::try_from(*limit).unwrap_or(u32::MAX);
+        if let Ok(permits) = self.permits.acquire_many(all).await {
+            permits.forget();
+        }
+        self.permits.close();
+    }
+}
@@ -0,0 +1,45 @@
+use log::error;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// Shared by every clone of a transport, such that its listener task
+/// is stopped once the last handle onto the connection is dropped.
+#[derive(Debug)]
+pub(crate) struct ListenerHandle {
+    cancel: Arc<Notify>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ListenerHandle {
+    pub(crate) fn new(cancel: Arc<Notify>, task: JoinHandle<()>) -> ListenerHandle {
+        ListenerHandle {
+            cancel,
+            task: Mutex::new(Some(task)),
+        }
+    }
+
+    /// Signals the listener to stop, and waits for it to end. Only the first
+    /// call waits, as the task can only be joined once.
+    pub(crate) async fn stop(&self) {
+        self.cancel.notify_one();
+
+        let task = self
+            .task
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        if let Some(task) = task {
+            if let Err(err) = task.await {
+                error!("Listener task did not end cleanly: {err}");
+            }
+        }
+    }
+}
+
+impl Drop for ListenerHandle {
+    fn drop(&mut self) {
+        self.cancel.notify_one();
+    }
+}
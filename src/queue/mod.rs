@@ -1 +1,3 @@
-pub mod buffer;
+pub mod limiter;
+pub mod listener;
+pub mod router;
@@ -0,0 +1,233 @@
+use crate::core::QueueError::{SlotClosed, TransactionInUse};
+use crate::prelude::{Error, Header};
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::oneshot;
+
+type Response = (Header, Vec<u8>);
+
+/// Routes each response to the request awaiting it, keyed by transaction id.
+///
+/// A request reserves a [`Slot`] *before* its packet is sent, so a response can never
+/// arrive ahead of the party waiting on it. Once the response is delivered, the slot is
+/// consumed and nothing is retained. Responses for which no slot exists, such as those
+/// arriving after their request timed out, are counted and dropped.
+#[derive(Debug, Default)]
+pub struct Router {
+    slots: Mutex<HashMap<u16, Entry>>,
+    generation: AtomicU64,
+    unrouted: AtomicU64,
+}
+
+/// The sender for a registered transaction, tagged with the generation of the slot
+/// that registered it. Transaction ids are reused, so the id alone does not identify
+/// which slot an entry belongs to.
+#[derive(Debug)]
+struct Entry {
+    generation: u64,
+    sender: oneshot::Sender<Response>,
+}
+
+/// A reservation for the response to a single transaction.
+///
+/// Dropping the slot, whether after receiving the response or because the request was
+/// cancelled, releases its transaction id.
+#[derive(Debug)]
+pub struct Slot {
+    router: Arc<Router>,
+    id: u16,
+    generation: u64,
+    receiver: oneshot::Receiver<Response>,
+}
+
+/// The critical sections are short and never held across an await point,
+/// so the lock may be taken from within [`Drop`]. A poisoned lock still holds
+/// consistent data, as no section can panic part-way through an update.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Router {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Reserves the slot for the transaction `id`. This must be done before the
+    /// request is sent. Fails if a request with the same id is still in flight.
+    pub fn register(self: &Arc<Self>, id: u16) -> Result<Slot, Error> {
+        let (sender, receiver) = oneshot::channel();
+
+        let mut slots = lock(&self.slots);
+        if slots.contains_key(&id) {
+            return Err(Error::Queue(TransactionInUse));
+        }
+
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed);
+        slots.insert(id, Entry { generation, sender });
+        debug!("Registered slot for TxnID={id}");
+
+        Ok(Slot {
+            router: Arc::clone(self),
+            id,
+            generation,
+            receiver,
+        })
+    }
+
+    /// Delivers a response to the slot awaiting its transaction id, if any.
+    pub(crate) fn publish(&self, header: Header, packet: Vec<u8>) {
+        let identifier = header.transaction_id;
+        let sender = lock(&self.slots).remove(&identifier);
+
+        let delivered = match sender {
+            Some(entry) => entry.sender.send((header, packet)).is_ok(),
+            None => false,
+        };
+
+        if delivered {
+            debug!("Routed packet TxnID={identifier}");
+        } else {
+            self.unrouted.fetch_add(1, Ordering::Relaxed);
+            warn!("Dropped packet with no pending request. TxnID={identifier}");
+        }
+    }
+
+    /// Closes every pending slot, such that each request awaiting a
+    /// response fails immediately rather than waiting for its timeout.
+    pub(crate) fn close_all(&self) {
+        lock(&self.slots).clear();
+    }
+
+    /// The number of requests awaiting a response.
+    pub fn pending(&self) -> usize {
+        lock(&self.slots).len()
+    }
+
+    /// The number of responses dropped as no request was awaiting them.
+    pub fn unrouted(&self) -> u64 {
+        self.unrouted.load(Ordering::Relaxed)
+    }
+}
+
+impl Slot {
+    /// Waits for the response to this slot's transaction. The wait may be abandoned
+    /// and resumed, as when a request is retransmitted under the same id.
+    pub async fn response(&mut self) -> Result<Response, Error> {
+        let response = (&mut self.receiver)
+            .await
+            .map_err(|_| Error::Queue(SlotClosed))?;
+
+        debug!("Slot received response TxnID={}", self.id);
+        Ok(response)
+    }
+}
+
+impl Drop for Slot {
+    /// Releases the transaction id, unless the response was already delivered and
+    /// the id has since been registered by another request.
+    fn drop(&mut self) {
+        let mut slots = lock(&self.router.slots);
+        if slots
+            .get(&self.id)
+            .is_some_and(|entry| entry.generation == self.generation)
+        {
+            slots.remove(&self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::MODBUS_PROTOCOL_TCP;
+    use std::time::Duration;
+
+    fn header(transaction_id: u16) -> Header {
+        Header {
+            transaction_id,
+            protocol_id: MODBUS_PROTOCOL_TCP,
+            length: 2,
+            unit_id: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn response_before_wait() {
+        let router = Router::new();
+
+        let mut slot = router.register(1).expect("Must register");
+        router.publish(header(1), vec![0x01]);
+
+        let (response, packet) = slot.response().await.expect("Must receive");
+        assert_eq!(response, header(1));
+        assert_eq!(packet, vec![0x01]);
+
+        assert_eq!(router.pending(), 0);
+        assert_eq!(router.unrouted(), 0);
+    }
+
+    #[tokio::test]
+    async fn cleans_up_when_cancelled() {
+        let router = Router::new();
+
+        let mut slot = router.register(1).expect("Must register");
+        let waited = tokio::time::timeout(Duration::from_millis(50), slot.response()).await;
+        assert!(waited.is_err());
+        assert_eq!(router.pending(), 1);
+
+        // The slot outlives an abandoned wait, and is only released once dropped.
+        drop(slot);
+        assert_eq!(router.pending(), 0);
+
+        // A late response is counted and dropped, not retained.
+        router.publish(header(1), vec![0x01]);
+        assert_eq!(router.pending(), 0);
+        assert_eq!(router.unrouted(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_duplicate_transaction() {
+        let router = Router::new();
+
+        let _slot = router.register(1).expect("Must register");
+        assert!(matches!(
+            router.register(1),
+            Err(Error::Queue(TransactionInUse))
+        ));
+    }
+
+    #[tokio::test]
+    async fn stale_slot_keeps_reused_id() {
+        let router = Router::new();
+
+        let mut first = router.register(1).expect("Must register");
+        router.publish(header(1), vec![0x01]);
+        first.response().await.expect("Must receive");
+
+        // The id is free once its response is delivered, and may be reused
+        // before the original slot is dropped.
+        let mut second = router.register(1).expect("Must register");
+        drop(first);
+        assert_eq!(router.pending(), 1);
+
+        router.publish(header(1), vec![0x02]);
+        let (_, packet) = second.response().await.expect("Must receive");
+        assert_eq!(packet, vec![0x02]);
+    }
+
+    #[tokio::test]
+    async fn closing_fails_pending() {
+        let router = Router::new();
+
+        let mut slot = router.register(1).expect("Must register");
+        router.close_all();
+
+        assert!(matches!(
+            slot.response().await,
+            Err(Error::Queue(SlotClosed))
+        ));
+        assert_eq!(router.pending(), 0);
+    }
+}
@@ -5,3 +5,142 @@ pub fn assert_correct_address() {
     assert_eq!(AIN55.address, 110);
     assert_eq!(AIN55.data_type, LabJackDataType::Float32);
 }
+
+#[test]
+#[cfg(feature = "beta-registers")]
+pub fn assert_beta_registers() {
+    assert_eq!(WIFI_SCAN_START.address, 49406);
+    assert_eq!(WIFI_SCAN_START.name, RegisterList::WifiScanStart);
+
+    // Ranged beta registers are expanded like any other.
+    assert_eq!(BATTERY_RAM16.address, BATTERY_RAM0.address + 32);
+}
+
+#[test]
+pub fn lookup_by_name() {
+    let register = Register::by_name("AIN3").expect("Must exist");
+    assert_eq!(register.name, RegisterList::Ain3);
+    assert_eq!(register.address, AIN3.address);
+
+    let register = Register::by_name("dio_ef_clock0_divisor").expect("Must ignore case");
+    assert_eq!(register.name, RegisterList::DioEfClock0Divisor);
+
+    // Alternative names resolve to the register they alias.
+    let register = Register::by_name("DIO8").expect("Must resolve alias");
+    assert_eq!(register.name, RegisterList::Eio0);
+    assert_eq!(register.address, EIO0.address);
+
+    assert!(Register::by_name("AIN100000").is_none());
+}
+
+#[test]
+pub fn lookup_by_address() {
+    let register = Register::by_address(AIN55.address).expect("Must exist");
+    assert_eq!(register.name, RegisterList::Ain55);
+    assert_eq!(register.data_type, LabJackDataType::Float32);
+
+    // The second half of a 32-bit register does not start a register.
+    assert!(Register::by_address(AIN55.address + 1).is_none());
+}
+
+#[test]
+pub fn register_list_round_trip() {
+    assert_eq!(RegisterList::Fio0.name(), "FIO0");
+    assert_eq!(RegisterList::Fio0.register().address, FIO0.address);
+
+    let name: RegisterList = "FIO0".parse().expect("Must parse");
+    assert_eq!(name, RegisterList::Fio0);
+
+    let parsed = "NOT_A_REGISTER".parse::<Register>();
+    assert!(
+        matches!(parsed, Err(Error::InvalidData(Reason::UnknownRegister(ref name))) if name == "NOT_A_REGISTER")
+    );
+}
+
+#[test]
+pub fn register_metadata() {
+    let metadata = AIN3.metadata();
+    assert!(metadata.streamable);
+    assert!(!metadata.is_buffer);
+    assert_eq!(metadata.display_name, Some("Analog In 3"));
+
+    assert!(SPI_DATA_TX.metadata().is_buffer);
+    assert!(STREAM_ENABLE.metadata().uses_ram);
+    assert!(!DAC0.metadata().streamable);
+
+    assert_eq!(AIN3_NEGATIVE_CH.default_value, Some(199.0));
+    assert_eq!(AIN0.default_value, None);
+}
+
+#[test]
+fn register_compatibility() {
+    assert_eq!(
+        POWER_MODE.name.compatibility(),
+        &[DeviceCompatibility {
+            device: DeviceType::T7,
+            min_firmware: Some(1.0224),
+        }]
+    );
+
+    let devices = PRODUCT_ID.name.compatibility();
+    assert!(devices.iter().any(|c| c.device == DeviceType::DIGIT));
+
+    assert!(AIN0.check_compatibility(DeviceType::T4, None).is_ok());
+    assert!(DIO_ANALOG_ENABLE
+        .check_compatibility(DeviceType::T7, None)
+        .is_err());
+    assert!(DIO_ANALOG_ENABLE
+        .check_compatibility(DeviceType::TSERIES, None)
+        .is_ok());
+}
+
+#[test]
+fn register_families() {
+    let register = ain(3).expect("AIN3 must exist");
+    assert_eq!(register.address, AIN3.address);
+    assert_eq!(register.name, RegisterList::Ain3);
+
+    assert_eq!(ain(254).map(|r| r.address), Some(AIN254.address));
+    assert!(ain(255).is_none());
+
+    assert_eq!(
+        dio_ef_config_a(22).map(|r| r.name),
+        Some(RegisterList::Dio22EfConfigA)
+    );
+    assert!(dio_ef_config_a(23).is_none());
+    assert_eq!(
+        dio_ef_config_a(22).map(|r| r.address),
+        Some(DIO22_EF_CONFIG_A.address)
+    );
+
+    // Successive registers of a family are spaced by the size of their data type.
+    let addresses = (0..)
+        .map_while(stream_scanlist_address)
+        .map(|r| r.address)
+        .collect::<Vec<_>>();
+    assert_eq!(addresses.len(), 128);
+    assert!(addresses.windows(2).all(|pair| pair[1] - pair[0] == 2));
+
+    assert_eq!(
+        ain_ef_index(0).map(|r| r.name),
+        Some(RegisterList::Ain0EfIndex)
+    );
+}
+
+#[test]
+fn register_tags() {
+    assert_eq!(AIN0.tags(), &["AIN", "CORE"]);
+
+    let streaming = registers_with_tag("STREAM").collect::<Vec<_>>();
+    assert!(streaming
+        .iter()
+        .any(|register| register.name == RegisterList::StreamEnable));
+    assert!(!streaming
+        .iter()
+        .any(|register| register.name == RegisterList::Ain0));
+
+    assert_eq!(registers_with_tag("not_a_tag").count(), 0);
+
+    let url = AIN0.doc_url().expect("AIN is documented");
+    assert!(url.contains("analog-inputs"), "url={url}");
+}